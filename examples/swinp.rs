@@ -0,0 +1,46 @@
+//! Reads a switch input request, prints its fields and answers it.
+//!
+//! Run with `cargo run --example swinp`.
+use std::io::Cursor;
+
+use jvs_packets::jvs::{command, RequestPacket, ResponsePacket};
+use jvs_packets::{Packet, ReadPacket, Report, ReportField, WritePacket};
+
+fn main() -> std::io::Result<()> {
+    // A SWINP request for 2 players with 2 bytes each, addressed to the node 0x01.
+    let mut reader = Cursor::new([0xE0, 0x01, 0x04, 0x20, 0x02, 0x02, 0x29]);
+    let mut request: RequestPacket = RequestPacket::new();
+    reader.read_packet(&mut request)?;
+    request.validate()?;
+
+    println!("request: {:?}", request);
+    println!(
+        "dest: {:#04x}, command: {:#04x}, params: {:02X?}",
+        request.dest(),
+        request.command(),
+        &request.data()[1..]
+    );
+    assert_eq!(request.command(), command::SWINP);
+
+    // Test switch on, player 1 holds start, player 2 holds the first push button.
+    let mut response: ResponsePacket = ResponsePacket::new();
+    response
+        .set_sync()
+        .set_dest(0x00)
+        .set_report(Report::Normal)
+        .finalize(&[0x80, 0x80, 0x00, 0x02, 0x00]);
+
+    let mut wire = Vec::new();
+    wire.write_packet(&response)?;
+    println!("response on the wire: {:02X?}", wire);
+
+    let switches = ResponsePacket::<[u8; 256]>::from_wire(&wire)?.parse_switches(2, 2)?;
+    println!(
+        "test: {}, player 1 start: {}, player 2 push 1: {}",
+        switches.test(),
+        switches.players[0].start(),
+        switches.players[1].button(1)
+    );
+
+    Ok(())
+}
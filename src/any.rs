@@ -0,0 +1,164 @@
+use crate::{FrameKind, Packet};
+
+/// A dyn-compatible view of a [`Packet`].
+///
+/// [`Packet`] has associated constants and const generic storage, so packets of different types can't be stored together.
+/// `AnyPacket` is implemented for every [`Packet`] and allows holding them as trait objects, e.g. `Vec<Box<dyn AnyPacket>>`.
+///
+/// The accessors forward to the [`Packet`] methods of the same name. If both traits are in scope,
+/// call them on concrete types with the fully qualified syntax, e.g. `Packet::data(&packet)`.
+///
+/// # Example
+/// ```
+/// use jvs_packets::{jvs::{RequestPacket, ResponsePacket}, AnyPacket};
+///
+/// let packets: Vec<Box<dyn AnyPacket>> = vec![
+///     Box::new(RequestPacket::<[u8; 256]>::from_slice(&[0xE0, 0x01, 0x02, 0x10, 0x13])),
+///     Box::new(ResponsePacket::<[u8; 256]>::from_slice(&[0xE0, 0x00, 0x03, 0x01, 0x10, 0x14])),
+/// ];
+///
+/// assert_eq!(packets[0].data(), &[0x10]);
+/// assert_eq!(packets[1].data(), &[0x10]);
+/// ```
+pub trait AnyPacket {
+    /// Returns the whole underlying buffer of the packet.
+    fn buffer(&self) -> &[u8];
+
+    /// Returns [`Packet::SIZE_INDEX`] of the packet type.
+    fn packet_size_index(&self) -> usize;
+
+    /// Returns [`Packet::DATA_BEGIN_INDEX`] of the packet type.
+    fn packet_data_begin_index(&self) -> usize;
+
+    /// Returns [`Packet::DESTINATION_INDEX`] of the packet type.
+    fn packet_destination_index(&self) -> usize;
+
+    /// See [`Packet::frame_kind`].
+    fn frame_kind(&self) -> FrameKind;
+
+    /// See [`Packet::len_of_packet`].
+    fn len_of_packet(&self) -> usize;
+
+    /// See [`Packet::as_slice`].
+    fn as_slice(&self) -> &[u8];
+
+    /// See [`Packet::size`].
+    fn size(&self) -> u8;
+
+    /// See [`Packet::dest`].
+    fn dest(&self) -> u8;
+
+    /// See [`Packet::data`].
+    fn data(&self) -> &[u8];
+
+    /// See [`Packet::checksum`].
+    fn checksum(&self) -> u8;
+}
+
+impl<P: Packet> AnyPacket for P {
+    fn buffer(&self) -> &[u8] {
+        self.as_ref()
+    }
+
+    fn packet_size_index(&self) -> usize {
+        self.size_index()
+    }
+
+    fn packet_data_begin_index(&self) -> usize {
+        self.data_begin_index()
+    }
+
+    fn packet_destination_index(&self) -> usize {
+        self.destination_index()
+    }
+
+    fn frame_kind(&self) -> FrameKind {
+        Packet::frame_kind(self)
+    }
+
+    fn len_of_packet(&self) -> usize {
+        Packet::len_of_packet(self)
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        Packet::as_slice(self)
+    }
+
+    fn size(&self) -> u8 {
+        Packet::size(self)
+    }
+
+    fn dest(&self) -> u8 {
+        Packet::dest(self)
+    }
+
+    fn data(&self) -> &[u8] {
+        Packet::data(self)
+    }
+
+    fn checksum(&self) -> u8 {
+        Packet::checksum(self)
+    }
+}
+
+#[cfg(all(test, feature = "jvs", feature = "jvs_modified"))]
+mod tests {
+    use super::*;
+    use crate::{jvs, jvs_modified};
+
+    #[test]
+    fn test_any_packet_vec() {
+        let request =
+            jvs::RequestPacket::<[u8; 256]>::from_slice(&[0xE0, 0xFF, 0x03, 0x01, 0x02, 0x05]);
+        let response = jvs::ResponsePacket::<[u8; 16]>::from_slice(&[
+            0xE0, 0xFF, 0x04, 0x01, 0x01, 0x02, 0x07,
+        ]);
+        let modified = jvs_modified::RequestPacket::<[u8; 32]>::from_slice(&[
+            0xE0, 0x06, 0xFF, 0x01, 0x02, 0x01, 0x02, 0x0B,
+        ]);
+        let packets: Vec<Box<dyn AnyPacket>> =
+            vec![Box::new(request), Box::new(response), Box::new(modified)];
+
+        assert_eq!(packets[0].as_slice(), [0xE0, 0xFF, 0x03, 0x01, 0x02, 0x05]);
+        assert_eq!(packets[0].data(), &[0x01, 0x02]);
+        assert_eq!(packets[1].size(), 0x04);
+        assert_eq!(packets[1].data(), &[0x01, 0x02]);
+        assert_eq!(packets[1].checksum(), 0x07);
+        assert_eq!(packets[2].dest(), 0xFF);
+        assert_eq!(packets[2].data(), &[0x01, 0x02]);
+        assert_eq!(packets[2].checksum(), 0x0B);
+    }
+
+    #[test]
+    fn test_any_packet_matches_packet() {
+        // The SIZE byte points past the end of the buffer.
+        let packet = jvs::RequestPacket::<[u8; 8]>::from_slice(&[0xE0, 0x01, 0x20, 0x10, 0x11]);
+        let any: &dyn AnyPacket = &packet;
+
+        assert_eq!(any.len_of_packet(), Packet::len_of_packet(&packet));
+        assert_eq!(any.as_slice(), Packet::as_slice(&packet));
+        assert_eq!(any.data(), Packet::data(&packet));
+        assert_eq!(any.checksum(), Packet::checksum(&packet));
+    }
+
+    #[test]
+    fn test_any_packet_frame_kind() {
+        let packets: [Box<dyn AnyPacket>; 4] = [
+            Box::new(jvs::RequestPacket::<[u8; 16]>::new()),
+            Box::new(jvs::ResponsePacket::<[u8; 16]>::new()),
+            Box::new(jvs_modified::RequestPacket::<[u8; 16]>::new()),
+            Box::new(jvs_modified::ResponsePacket::<[u8; 16]>::new()),
+        ];
+        let kinds = packets.map(|packet| packet.frame_kind());
+
+        assert_eq!(
+            kinds,
+            [
+                FrameKind::Request,
+                FrameKind::Response,
+                FrameKind::Request,
+                FrameKind::Response
+            ]
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use std::io;
+use std::marker::PhantomData;
+
+use bytes::{Buf, BufMut, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::{Packet, ReadState, WritePacket};
+
+/// A [`tokio_util::codec`] implementation for packets of type `P`.
+///
+/// The decoder yields complete packets and leaves partial frames in the buffer,
+/// the encoder escapes packets and calculates their checksum, like [`WritePacket::write_packet_with_checksum`].
+/// Wrap a serial port into a [`tokio_util::codec::Framed`] to get a `Stream` and a `Sink` of packets.
+#[derive(Debug)]
+pub struct JvsCodec<P> {
+    _packet: PhantomData<fn() -> P>,
+}
+
+impl<P> JvsCodec<P> {
+    pub fn new() -> Self {
+        Self {
+            _packet: PhantomData,
+        }
+    }
+}
+
+impl<P> Default for JvsCodec<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P> Clone for JvsCodec<P> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<P: Packet + Default> Decoder for JvsCodec<P> {
+    type Item = P;
+    type Error = io::Error;
+
+    /// Decodes the first complete frame in `src`.
+    ///
+    /// Bytes before the SYNC byte and malformed frames, e.g. the ones that don't fit into the packet, are skipped.
+    /// Skipped bytes are removed from `src`, so only the beginning of an incomplete frame is kept between calls
+    /// and line noise doesn't accumulate in the buffer.
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<P>> {
+        let mut packet = P::default();
+        let mut state = ReadState::new();
+        let mut frame_start = None;
+        for (i, &b) in src.iter().enumerate() {
+            if b == P::SYNC {
+                frame_start = Some(i);
+            }
+            match state.push(&mut packet, b) {
+                Ok(true) => {
+                    src.advance(i + 1);
+                    return Ok(Some(packet));
+                }
+                Ok(false) => {}
+                Err(_) => frame_start = None,
+            }
+        }
+
+        src.advance(frame_start.unwrap_or(src.len()));
+        Ok(None)
+    }
+}
+
+impl<P: Packet> Encoder<P> for JvsCodec<P> {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: P, dst: &mut BytesMut) -> io::Result<()> {
+        dst.reserve(item.wire_len());
+        dst.writer().write_packet_with_checksum(&item)?;
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "jvs"))]
+mod tests {
+    use futures_util::{SinkExt, StreamExt};
+    use tokio_util::codec::Framed;
+
+    use super::*;
+    use crate::jvs::RequestPacket;
+
+    #[tokio::test]
+    async fn test_codec_framed() {
+        let (client, server) = tokio::io::duplex(64);
+        let mut client = Framed::new(client, JvsCodec::<RequestPacket<[u8; 16]>>::new());
+        let mut server = Framed::new(server, JvsCodec::<RequestPacket<[u8; 16]>>::new());
+
+        let first = RequestPacket::<[u8; 16]>::from_slice(&[0xE0, 0xFF, 0x03, 0xE0, 0x01, 0xE3]);
+        let second = RequestPacket::<[u8; 16]>::from_slice(&[0xE0, 0x01, 0x02, 0xD0, 0xD3]);
+        client.send(first.clone()).await.unwrap();
+        client.send(second.clone()).await.unwrap();
+
+        let read = server.next().await.unwrap().unwrap();
+        assert_eq!(read.as_slice(), first.as_slice());
+        let read = server.next().await.unwrap().unwrap();
+        assert_eq!(read.as_slice(), second.as_slice());
+    }
+
+    #[test]
+    fn test_codec_skips_noise() {
+        let mut codec = JvsCodec::<RequestPacket<[u8; 16]>>::new();
+        let mut src = BytesMut::new();
+
+        for _ in 0..10 {
+            src.extend_from_slice(&[0x55; 100]);
+            assert!(codec.decode(&mut src).unwrap().is_none());
+            assert!(src.is_empty());
+        }
+
+        // Noise, an oversized frame followed by noise, and the beginning of a valid frame.
+        src.extend_from_slice(&[0x55; 10]);
+        src.extend_from_slice(&[0xE0, 0x01, 0x20]);
+        src.extend_from_slice(&[0x55; 100]);
+        src.extend_from_slice(&[0xE0, 0x01, 0x02]);
+        assert!(codec.decode(&mut src).unwrap().is_none());
+        assert_eq!(src[..], [0xE0, 0x01, 0x02]);
+
+        src.extend_from_slice(&[0x10, 0x13]);
+        let packet = codec.decode(&mut src).unwrap().unwrap();
+        assert_eq!(packet.as_slice(), [0xE0, 0x01, 0x02, 0x10, 0x13]);
+        assert!(src.is_empty());
+    }
+}
@@ -0,0 +1,298 @@
+use std::io::{self, Read, Write};
+use std::ops::Deref;
+
+use crate::{AnyPacket, Packet, PacketError, ReadPacket, WritePacket};
+
+#[cfg(feature = "jvs")]
+use crate::jvs;
+#[cfg(feature = "jvs_modified")]
+use crate::jvs_modified;
+
+/// The protocol and the direction of a [`DynPacket`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PacketKind {
+    /// [`jvs::RequestPacket`].
+    #[cfg(feature = "jvs")]
+    JvsRequest,
+    /// [`jvs::ResponsePacket`].
+    #[cfg(feature = "jvs")]
+    JvsResponse,
+    /// [`jvs_modified::RequestPacket`].
+    #[cfg(feature = "jvs_modified")]
+    ModifiedRequest,
+    /// [`jvs_modified::ResponsePacket`].
+    #[cfg(feature = "jvs_modified")]
+    ModifiedResponse,
+}
+
+/// A `Vec<u8>` backed packet, which kind and capacity are chosen at runtime, e.g. from a config file.
+///
+/// The frame layout of [`crate::Packet`] is defined by associated constants, so a packet with a runtime kind can't implement it.
+/// Instead, each variant holds a concrete packet, that implements the whole [`crate::Packet`] trait.
+/// `DynPacket` dereferences to [`AnyPacket`] for the common read-only accessors and forwards the methods,
+/// that build and modify a frame, so they work without matching on the variants.
+///
+/// # Example
+/// ```
+/// use jvs_packets::{AnyPacket, DynPacket, PacketKind};
+///
+/// let capacity = 64; // e.g. read from a config file
+/// let mut packet = DynPacket::with_capacity(capacity, PacketKind::JvsRequest);
+/// let mut reader = std::io::Cursor::new([0xE0, 0xFF, 0x03, 0x01, 0x02, 0x05]);
+/// packet.read_from(&mut reader).unwrap();
+///
+/// assert_eq!(packet.data(), &[0x01, 0x02]);
+/// ```
+#[derive(Debug, Clone)]
+pub enum DynPacket {
+    #[cfg(feature = "jvs")]
+    JvsRequest(jvs::RequestPacket<Vec<u8>>),
+    #[cfg(feature = "jvs")]
+    JvsResponse(jvs::ResponsePacket<Vec<u8>>),
+    #[cfg(feature = "jvs_modified")]
+    ModifiedRequest(jvs_modified::RequestPacket<Vec<u8>>),
+    #[cfg(feature = "jvs_modified")]
+    ModifiedResponse(jvs_modified::ResponsePacket<Vec<u8>>),
+}
+
+/// Evaluates `$body` with `$p` bound to the concrete packet of every variant.
+macro_rules! dispatch {
+    ($packet:expr, $p:ident => $body:expr) => {
+        match $packet {
+            #[cfg(feature = "jvs")]
+            DynPacket::JvsRequest($p) => $body,
+            #[cfg(feature = "jvs")]
+            DynPacket::JvsResponse($p) => $body,
+            #[cfg(feature = "jvs_modified")]
+            DynPacket::ModifiedRequest($p) => $body,
+            #[cfg(feature = "jvs_modified")]
+            DynPacket::ModifiedResponse($p) => $body,
+        }
+    };
+}
+
+impl DynPacket {
+    /// Creates a packet of the given kind backed by a zeroed [`Vec`] of `capacity` bytes.
+    ///
+    /// # Panics
+    /// Like `with_capacity` of the concrete packets, if `capacity` is too small to hold a minimal frame of the packet.
+    pub fn with_capacity(capacity: usize, kind: PacketKind) -> Self {
+        match kind {
+            #[cfg(feature = "jvs")]
+            PacketKind::JvsRequest => Self::JvsRequest(jvs::RequestPacket::with_capacity(capacity)),
+            #[cfg(feature = "jvs")]
+            PacketKind::JvsResponse => {
+                Self::JvsResponse(jvs::ResponsePacket::with_capacity(capacity))
+            }
+            #[cfg(feature = "jvs_modified")]
+            PacketKind::ModifiedRequest => {
+                Self::ModifiedRequest(jvs_modified::RequestPacket::with_capacity(capacity))
+            }
+            #[cfg(feature = "jvs_modified")]
+            PacketKind::ModifiedResponse => {
+                Self::ModifiedResponse(jvs_modified::ResponsePacket::with_capacity(capacity))
+            }
+        }
+    }
+
+    /// Creates a packet of the given kind backed by a zeroed [`Vec`] of `capacity` bytes.
+    ///
+    /// Returns [`None`] if `capacity` is too small to hold a minimal frame of the packet.
+    pub fn try_with_capacity(capacity: usize, kind: PacketKind) -> Option<Self> {
+        Some(match kind {
+            #[cfg(feature = "jvs")]
+            PacketKind::JvsRequest => Self::JvsRequest(jvs::RequestPacket::try_new(capacity)?),
+            #[cfg(feature = "jvs")]
+            PacketKind::JvsResponse => Self::JvsResponse(jvs::ResponsePacket::try_new(capacity)?),
+            #[cfg(feature = "jvs_modified")]
+            PacketKind::ModifiedRequest => {
+                Self::ModifiedRequest(jvs_modified::RequestPacket::try_new(capacity)?)
+            }
+            #[cfg(feature = "jvs_modified")]
+            PacketKind::ModifiedResponse => {
+                Self::ModifiedResponse(jvs_modified::ResponsePacket::try_new(capacity)?)
+            }
+        })
+    }
+
+    /// Returns the kind of the packet.
+    pub fn kind(&self) -> PacketKind {
+        match self {
+            #[cfg(feature = "jvs")]
+            Self::JvsRequest(_) => PacketKind::JvsRequest,
+            #[cfg(feature = "jvs")]
+            Self::JvsResponse(_) => PacketKind::JvsResponse,
+            #[cfg(feature = "jvs_modified")]
+            Self::ModifiedRequest(_) => PacketKind::ModifiedRequest,
+            #[cfg(feature = "jvs_modified")]
+            Self::ModifiedResponse(_) => PacketKind::ModifiedResponse,
+        }
+    }
+
+    /// Reads a packet from `reader`. See [`ReadPacket::read_packet`].
+    pub fn read_from(&mut self, reader: &mut impl Read) -> io::Result<usize> {
+        dispatch!(self, p => reader.read_packet(p))
+    }
+
+    /// Writes the packet to `writer`. See [`WritePacket::write_packet`].
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<usize> {
+        dispatch!(self, p => writer.write_packet(p))
+    }
+
+    /// See [`Packet::set_sync`].
+    pub fn set_sync(&mut self) -> &mut Self {
+        dispatch!(&mut *self, p => { p.set_sync(); });
+        self
+    }
+
+    /// See [`Packet::set_dest`].
+    pub fn set_dest(&mut self, dest: u8) -> &mut Self {
+        dispatch!(&mut *self, p => { p.set_dest(dest); });
+        self
+    }
+
+    /// See [`Packet::set_data`].
+    pub fn set_data(&mut self, data: &[u8]) -> &mut Self {
+        dispatch!(&mut *self, p => { p.set_data(data); });
+        self
+    }
+
+    /// See [`Packet::data_mut`].
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        dispatch!(self, p => p.data_mut())
+    }
+
+    /// See [`Packet::fill_data`].
+    pub fn fill_data(&mut self, byte: u8, count: usize) -> Result<&mut Self, PacketError> {
+        dispatch!(&mut *self, p => p.fill_data(byte, count).map(|_| ()))?;
+        Ok(self)
+    }
+
+    /// See [`Packet::extend_data`].
+    pub fn extend_data(&mut self, extra: &[u8]) -> Result<&mut Self, PacketError> {
+        dispatch!(&mut *self, p => p.extend_data(extra).map(|_| ()))?;
+        Ok(self)
+    }
+
+    /// See [`Packet::truncate_data`].
+    pub fn truncate_data(&mut self, new_len: usize) -> &mut Self {
+        dispatch!(&mut *self, p => { p.truncate_data(new_len); });
+        self
+    }
+
+    /// See [`Packet::set_checksum`].
+    pub fn set_checksum(&mut self, checksum: u8) -> &mut Self {
+        dispatch!(&mut *self, p => { p.set_checksum(checksum); });
+        self
+    }
+
+    /// See [`Packet::calculate_checksum`].
+    pub fn calculate_checksum(&mut self) -> &mut Self {
+        dispatch!(&mut *self, p => { p.calculate_checksum(); });
+        self
+    }
+
+    /// See [`Packet::recalculate`].
+    pub fn recalculate(&mut self) -> &mut Self {
+        dispatch!(&mut *self, p => { p.recalculate(); });
+        self
+    }
+
+    /// See [`Packet::finalize`].
+    pub fn finalize(&mut self, data: &[u8]) -> &mut Self {
+        dispatch!(&mut *self, p => { p.finalize(data); });
+        self
+    }
+
+    /// See [`Packet::clear`].
+    pub fn clear(&mut self) -> &mut Self {
+        dispatch!(&mut *self, p => { p.clear(); });
+        self
+    }
+
+    /// See [`Packet::reset`].
+    pub fn reset(&mut self) -> &mut Self {
+        dispatch!(&mut *self, p => { p.reset(); });
+        self
+    }
+
+    /// See [`Packet::verify_checksum`].
+    #[must_use]
+    pub fn verify_checksum(&self) -> bool {
+        dispatch!(self, p => p.verify_checksum())
+    }
+
+    /// See [`Packet::validate`].
+    pub fn validate(&self) -> Result<(), PacketError> {
+        dispatch!(self, p => p.validate())
+    }
+}
+
+impl Deref for DynPacket {
+    type Target = dyn AnyPacket;
+
+    fn deref(&self) -> &Self::Target {
+        dispatch!(self, p => p)
+    }
+}
+
+#[cfg(all(test, feature = "jvs", feature = "jvs_modified"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dyn_packet_runtime_capacity() {
+        for capacity in [8, 300] {
+            let mut packet = DynPacket::with_capacity(capacity, PacketKind::ModifiedRequest);
+            assert_eq!(packet.kind(), PacketKind::ModifiedRequest);
+            assert_eq!(packet.buffer().len(), capacity);
+
+            let frame = [0xE0, 0x06, 0xFF, 0x01, 0x02, 0x01, 0x02, 0x0B];
+            packet.read_from(&mut std::io::Cursor::new(frame)).unwrap();
+            assert_eq!(packet.as_slice(), frame);
+            assert_eq!(packet.data(), &[0x01, 0x02]);
+
+            let mut wire = vec![];
+            packet.write_to(&mut wire).unwrap();
+            assert_eq!(wire, frame);
+        }
+    }
+
+    #[test]
+    fn test_dyn_packet_try_with_capacity() {
+        assert!(DynPacket::try_with_capacity(0, PacketKind::JvsRequest).is_none());
+        assert!(DynPacket::try_with_capacity(4, PacketKind::JvsResponse).is_none());
+        let packet = DynPacket::try_with_capacity(4, PacketKind::JvsRequest).unwrap();
+        assert_eq!(packet.kind(), PacketKind::JvsRequest);
+        assert!(packet.data().is_empty());
+    }
+
+    #[test]
+    fn test_dyn_packet_build() {
+        let mut packet = DynPacket::with_capacity(16, PacketKind::ModifiedRequest);
+        packet.set_sync().set_dest(0xFF).finalize(&[0x01, 0x02]);
+        assert_eq!(packet.data(), &[0x01, 0x02]);
+        assert!(packet.verify_checksum());
+
+        packet.extend_data(&[0x03]).unwrap().calculate_checksum();
+        assert_eq!(packet.data(), &[0x01, 0x02, 0x03]);
+        packet.data_mut()[0] = 0x10;
+        packet.recalculate();
+        assert_eq!(packet.validate(), Ok(()));
+        assert!(packet.fill_data(0x00, 16).is_err());
+    }
+
+    #[test]
+    fn test_dyn_packet_large_frame() {
+        let mut frame = vec![0xE0, 0x01, 0xFF];
+        frame.extend_from_slice(&[0x10; 0xFE]);
+        frame.push(0xFE);
+        let mut packet = DynPacket::with_capacity(frame.len(), PacketKind::JvsRequest);
+        packet.read_from(&mut std::io::Cursor::new(&frame)).unwrap();
+        assert_eq!(packet.data().len(), 0xFE);
+        assert_eq!(packet.checksum(), 0xFE);
+
+        let mut packet = DynPacket::with_capacity(frame.len() - 1, PacketKind::JvsResponse);
+        assert!(packet.read_from(&mut std::io::Cursor::new(&frame)).is_err());
+    }
+}
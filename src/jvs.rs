@@ -14,42 +14,592 @@
 //! [REPORT]: crate::Report
 use std::convert::{AsMut, AsRef};
 
-use crate::{impl_required_packet_blocks, Packet, ReportField};
+use crate::{impl_required_packet_blocks, FrameKind, Packet, PacketError, ReportField};
 
-#[derive(Debug, Clone)]
-pub struct RequestPacket<const N: usize = 256> {
-    inner: [u8; N],
+/// JVS command codes, that are placed in the first DATA byte of a request.
+pub mod command {
+    /// I/O identify. The response is a null-terminated ASCII string, see [`super::BoardInfo`].
+    pub const IOIDENT: u8 = 0x10;
+    /// Command format revision. The response is a BCD version byte, see [`super::Version`].
+    pub const CMDREV: u8 = 0x11;
+    /// JVS revision. The response is a BCD version byte.
+    pub const JVSREV: u8 = 0x12;
+    /// Communications version. The response is a BCD version byte.
+    pub const COMMVER: u8 = 0x13;
+    /// Feature check. The response is a list of functions, see [`super::Functions`].
+    pub const FEATCHK: u8 = 0x14;
+    /// Switch inputs. Params: players, bytes per player. See [`super::SwitchInput`].
+    pub const SWINP: u8 = 0x20;
+    /// Coin inputs. Params: slot count. See [`super::CoinInput`].
+    pub const COININP: u8 = 0x21;
+    /// Analog inputs. Params: channel count. The response holds 2 bytes (MSB first) per channel.
+    pub const ANLINP: u8 = 0x22;
+    /// Coin counter decrement. Params: slot, 2 bytes (MSB first) amount.
+    pub const COINDEC: u8 = 0x30;
+    /// General purpose output 1. Params: byte count, output data.
+    pub const OUTPUT1: u8 = 0x32;
+    /// Analog output. Params: channel count, 2 bytes (MSB first) per channel.
+    pub const ANLOUT: u8 = 0x33;
+}
+
+/// A request packet (master -> slave).
+///
+/// The array storage must be large enough to hold at least a minimal frame, otherwise the code fails to compile:
+/// ```compile_fail
+/// use jvs_packets::jvs::RequestPacket;
+///
+/// let packet = RequestPacket::<[u8; 2]>::new();
+/// ```
+#[derive(Clone)]
+pub struct RequestPacket<S = [u8; 256]> {
+    inner: S,
 }
 
-impl<const N: usize> Packet for RequestPacket<N> {
+impl<S: AsRef<[u8]> + AsMut<[u8]>> Packet for RequestPacket<S> {
     const DATA_BEGIN_INDEX: usize = 3;
     const SIZE_INDEX: usize = 2;
     const DESTINATION_INDEX: usize = 1;
+    const KIND: FrameKind = FrameKind::Request;
+}
+
+impl<S: AsRef<[u8]> + AsMut<[u8]>> RequestPacket<S> {
+    /// Returns a command code, which is the first DATA byte.
+    pub fn command(&self) -> u8 {
+        self.as_ref()[Self::DATA_BEGIN_INDEX]
+    }
+
+    /// Sets a command code, which is the first DATA byte.
+    ///
+    /// The rest of the data is preserved. If the packet has no data, the size byte is set to hold the command.
+    pub fn set_command(&mut self, command: u8) -> &mut Self {
+        if self.data_is_empty() {
+            return self.set_data(&[command]);
+        }
+        self.as_mut()[Self::DATA_BEGIN_INDEX] = command;
+        self
+    }
+}
+
+impl<const N: usize> RequestPacket<[u8; N]> {
+    /// Creates a request with a single command and computes the checksum.
+    fn with_command(dest: u8, command: u8, params: &[u8]) -> Self {
+        let mut data = Vec::with_capacity(params.len() + 1);
+        data.push(command);
+        data.extend_from_slice(params);
+
+        let mut packet = Self::new();
+        packet
+            .set_sync()
+            .set_dest(dest)
+            .set_data(&data)
+            .calculate_checksum();
+        packet
+    }
+
+    /// Creates a general purpose output request ([`command::OUTPUT1`]), that sets the output bytes to `data`.
+    ///
+    /// # Panics
+    /// If `data` is longer than 255 bytes or doesn't fit into the packet.
+    pub fn gpio_output(dest: u8, data: &[u8]) -> Self {
+        let mut params = Vec::with_capacity(data.len() + 1);
+        params.push(u8::try_from(data.len()).expect("too many output bytes"));
+        params.extend_from_slice(data);
+        Self::with_command(dest, command::OUTPUT1, &params)
+    }
+
+    /// Creates an analog output request ([`command::ANLOUT`]), that sets channels starting from the first one to `values`.
+    ///
+    /// The values are written in big-endian byte order, as the spec requires.
+    ///
+    /// # Panics
+    /// If there are more than 255 values or they don't fit into the packet.
+    pub fn analog_output(dest: u8, values: &[u16]) -> Self {
+        let mut params = Vec::with_capacity(values.len() * 2 + 1);
+        params.push(u8::try_from(values.len()).expect("too many analog channels"));
+        for value in values {
+            params.extend_from_slice(&value.to_be_bytes());
+        }
+        Self::with_command(dest, command::ANLOUT, &params)
+    }
+
+    /// Creates an analog input request ([`command::ANLINP`]), that reads `channels` channels starting from the first one.
+    pub fn analog_input(dest: u8, channels: u8) -> Self {
+        Self::with_command(dest, command::ANLINP, &[channels])
+    }
+
+    /// Creates a coin decrement request ([`command::COINDEC`]), that subtracts `amount` from the counter of `slot`.
+    ///
+    /// Slots are numbered from 1. The amount is written in big-endian byte order, as the spec requires.
+    pub fn coin_decrement(dest: u8, slot: u8, amount: u16) -> Self {
+        let [hi, lo] = amount.to_be_bytes();
+        Self::with_command(dest, command::COINDEC, &[slot, hi, lo])
+    }
 }
 
 impl_required_packet_blocks!(RequestPacket);
 
-#[derive(Debug, Clone)]
-pub struct ResponsePacket<const N: usize = 256> {
-    inner: [u8; N],
+#[cfg(feature = "defmt")]
+impl<S: AsRef<[u8]> + AsMut<[u8]>> defmt::Format for RequestPacket<S> {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "RequestPacket {{ dest: {=u8:#04x}, size: {=u8}, data: {=[u8]:02x}, sum: {=u8:#04x} }}",
+            self.dest(),
+            self.size(),
+            self.data(),
+            self.checksum()
+        )
+    }
+}
+
+/// A response packet (slave -> master).
+///
+/// The DEST byte of a response holds the address of the master (`0x00`), not of the responding slave,
+/// and the frame has no source field. Since the bus is half-duplex and a slave only answers the request addressed to it,
+/// the responder is the destination of the request, that the response answers.
+#[derive(Clone)]
+pub struct ResponsePacket<S = [u8; 256]> {
+    inner: S,
 }
 
-impl<const N: usize> Packet for ResponsePacket<N> {
+impl<S: AsRef<[u8]> + AsMut<[u8]>> Packet for ResponsePacket<S> {
     const DATA_BEGIN_INDEX: usize = 4;
     const SIZE_INDEX: usize = 2;
     const DESTINATION_INDEX: usize = 1;
+    const KIND: FrameKind = FrameKind::Response;
 }
 
-impl<const N: usize> ReportField for ResponsePacket<N> {
+impl<S: AsRef<[u8]> + AsMut<[u8]>> ReportField for ResponsePacket<S> {
     const REPORT_INDEX: usize = 3;
 }
 
+impl<S: AsRef<[u8]> + AsMut<[u8]>> ResponsePacket<S> {
+    /// Parses the data of an [`command::IOIDENT`] response, which is a null-terminated ASCII identification string.
+    ///
+    /// # Errors
+    /// - [`PacketError::MissingTerminator`] if the data has no null byte.
+    /// - [`PacketError::NonAscii`] if the string contains a non-ASCII byte.
+    pub fn parse_identify(&self) -> Result<String, PacketError> {
+        let data = self.data();
+        let len = data
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or(PacketError::MissingTerminator)?;
+        let id = &data[..len];
+        if let Some(index) = id.iter().position(|b| !b.is_ascii()) {
+            return Err(PacketError::NonAscii {
+                index,
+                byte: id[index],
+            });
+        }
+
+        Ok(id.iter().map(|&b| b as char).collect())
+    }
+
+    /// Parses the data of a [`command::FEATCHK`] response into the list of functions, that the I/O board supports.
+    ///
+    /// See [`Functions`] for details.
+    pub fn parse_functions(&self) -> Vec<Function> {
+        Functions::new(self.data()).collect()
+    }
+
+    /// Parses the data of a [`command::SWINP`] response: the system byte, followed by `bytes_per_player` bytes for each player.
+    ///
+    /// Bytes after the switches of the last player are ignored.
+    ///
+    /// # Errors
+    /// - [`PacketError::InvalidArgument`] if `bytes_per_player` is `0` while `players` is not.
+    /// - [`PacketError::DataTooShort`] if the data doesn't hold the switches of every player.
+    pub fn parse_switches(
+        &self,
+        players: usize,
+        bytes_per_player: usize,
+    ) -> Result<SwitchInput, PacketError> {
+        if players > 0 && bytes_per_player == 0 {
+            return Err(PacketError::InvalidArgument(
+                "players must have at least one switch byte",
+            ));
+        }
+        let data = self.data();
+        let expected = 1 + players * bytes_per_player;
+        if data.len() < expected {
+            return Err(PacketError::DataTooShort {
+                len: data.len(),
+                expected,
+            });
+        }
+
+        Ok(SwitchInput {
+            system: data[0],
+            players: data[1..expected]
+                .chunks_exact(bytes_per_player.max(1))
+                .map(|bytes| PlayerSwitches {
+                    bytes: bytes.to_vec(),
+                })
+                .collect(),
+        })
+    }
+
+    /// Parses the data of a [`command::COININP`] response: 2 bytes (MSB first) for each of `slots` coin slots.
+    ///
+    /// Bytes after the last slot are ignored.
+    ///
+    /// # Errors
+    /// Returns [`PacketError::DataTooShort`] if the data doesn't hold every slot.
+    pub fn parse_coins(&self, slots: usize) -> Result<CoinInput, PacketError> {
+        let data = self.data();
+        let expected = slots * 2;
+        if data.len() < expected {
+            return Err(PacketError::DataTooShort {
+                len: data.len(),
+                expected,
+            });
+        }
+
+        Ok(CoinInput {
+            slots: data[..expected]
+                .chunks_exact(2)
+                .map(|b| CoinSlot::from_be_bytes([b[0], b[1]]))
+                .collect(),
+        })
+    }
+
+    /// Parses the data of a [`command::ANLINP`] response: 2 bytes (MSB first) for each of `channels` channels.
+    ///
+    /// The values are left-justified: a board with an `n` bit converter puts its value in the top `n` bits
+    /// and zeroes the rest, so `value >> (16 - n)` is the raw reading. Bytes after the last channel are ignored.
+    ///
+    /// # Errors
+    /// Returns [`PacketError::DataTooShort`] if the data doesn't hold every channel.
+    pub fn parse_analog(&self, channels: usize) -> Result<Vec<u16>, PacketError> {
+        let data = self.data();
+        let expected = channels * 2;
+        if data.len() < expected {
+            return Err(PacketError::DataTooShort {
+                len: data.len(),
+                expected,
+            });
+        }
+
+        Ok(data[..expected]
+            .chunks_exact(2)
+            .map(|b| u16::from_be_bytes([b[0], b[1]]))
+            .collect())
+    }
+
+    /// Parses the data of a [`command::CMDREV`], [`command::JVSREV`] or [`command::COMMVER`] response.
+    ///
+    /// # Errors
+    /// Returns [`PacketError::DataTooShort`] if the data is empty.
+    pub fn parse_version(&self) -> Result<Version, PacketError> {
+        match self.data().first() {
+            Some(&b) => Ok(Version::from_bcd(b)),
+            None => Err(PacketError::DataTooShort {
+                len: 0,
+                expected: 1,
+            }),
+        }
+    }
+}
+
+/// A version, that is encoded as a BCD byte, e.g. `0x13` is version 1.3.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    pub major: u8,
+    pub minor: u8,
+}
+
+impl Version {
+    /// Decodes a BCD version byte, where the high nibble is the major and the low nibble is the minor version.
+    pub fn from_bcd(b: u8) -> Self {
+        Self {
+            major: b >> 4,
+            minor: b & 0x0F,
+        }
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// Switch states of a [`command::SWINP`] response. Created by [`ResponsePacket::parse_switches`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwitchInput {
+    /// The system byte with the test and tilt switches.
+    pub system: u8,
+    pub players: Vec<PlayerSwitches>,
+}
+
+impl SwitchInput {
+    /// Returns `true` if the test switch is on.
+    pub fn test(&self) -> bool {
+        self.system & 0x80 != 0
+    }
+
+    /// Returns `true` if the tilt switch `n` (1 to 3) is on.
+    ///
+    /// # Panics
+    /// If `n` is not in `1..=3`.
+    pub fn tilt(&self, n: usize) -> bool {
+        assert!((1..=3).contains(&n), "tilt switch {} doesn't exist", n);
+        self.system & (0x80 >> n) != 0
+    }
+}
+
+/// Switch states of a single player.
+///
+/// The first byte holds the start, service and direction switches and the first two push buttons,
+/// the following bytes hold the rest of the push buttons, starting from the most significant bit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerSwitches {
+    bytes: Vec<u8>,
+}
+
+impl PlayerSwitches {
+    /// Returns the raw switch bytes of the player.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns the state of the switch at `bit`, counting from the most significant bit of the first byte.
+    /// Switches past the received bytes are off.
+    fn bit(&self, bit: usize) -> bool {
+        self.bytes
+            .get(bit / 8)
+            .is_some_and(|b| b & (0x80 >> (bit % 8)) != 0)
+    }
+
+    pub fn start(&self) -> bool {
+        self.bit(0)
+    }
+
+    pub fn service(&self) -> bool {
+        self.bit(1)
+    }
+
+    pub fn up(&self) -> bool {
+        self.bit(2)
+    }
+
+    pub fn down(&self) -> bool {
+        self.bit(3)
+    }
+
+    pub fn left(&self) -> bool {
+        self.bit(4)
+    }
+
+    pub fn right(&self) -> bool {
+        self.bit(5)
+    }
+
+    /// Returns `true` if the push button `n` (starting from 1) is pressed.
+    ///
+    /// # Panics
+    /// If `n` is `0`.
+    pub fn button(&self, n: usize) -> bool {
+        assert!(n > 0, "push buttons are numbered from 1");
+        self.bit(5 + n)
+    }
+}
+
+/// Coin slots of a [`command::COININP`] response. Created by [`ResponsePacket::parse_coins`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoinInput {
+    pub slots: Vec<CoinSlot>,
+}
+
+/// The state of a single coin slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CoinSlot {
+    pub condition: CoinCondition,
+    /// The coin count, which is 14 bits wide.
+    pub count: u16,
+}
+
+impl CoinSlot {
+    /// Decodes a slot, where the top 2 bits are the condition and the low 14 bits are the count.
+    pub fn from_be_bytes(bytes: [u8; 2]) -> Self {
+        let value = u16::from_be_bytes(bytes);
+        Self {
+            condition: CoinCondition::from_bits((value >> 14) as u8),
+            count: value & 0x3FFF,
+        }
+    }
+}
+
+/// The condition of a coin slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CoinCondition {
+    Normal,
+    Jammed,
+    Disconnected,
+    Busy,
+}
+
+impl CoinCondition {
+    /// Decodes the low 2 bits of `bits`.
+    fn from_bits(bits: u8) -> Self {
+        match bits & 0b11 {
+            0 => Self::Normal,
+            1 => Self::Jammed,
+            2 => Self::Disconnected,
+            _ => Self::Busy,
+        }
+    }
+}
+
+/// A function code of a [`command::FEATCHK`] response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FunctionCode {
+    /// Params: players, switches per player.
+    SwitchInput,
+    /// Params: coin slots.
+    CoinInput,
+    /// Params: channels, significant bits.
+    AnalogInput,
+    /// Params: channels.
+    RotaryInput,
+    KeycodeInput,
+    /// Params: X bits, Y bits, channels.
+    ScreenPositionInput,
+    /// Params: switches (MSB, LSB).
+    MiscSwitchInput,
+    /// Params: card slots.
+    CardSystem,
+    /// Params: channels.
+    MedalHopper,
+    /// Params: slots.
+    GeneralPurposeOutput,
+    /// Params: channels.
+    AnalogOutput,
+    /// Params: width, height, character type.
+    CharacterOutput,
+    Backup,
+    /// A function code, that is not defined by the spec.
+    Unknown(u8),
+}
+
+impl From<u8> for FunctionCode {
+    fn from(value: u8) -> Self {
+        match value {
+            0x01 => FunctionCode::SwitchInput,
+            0x02 => FunctionCode::CoinInput,
+            0x03 => FunctionCode::AnalogInput,
+            0x04 => FunctionCode::RotaryInput,
+            0x05 => FunctionCode::KeycodeInput,
+            0x06 => FunctionCode::ScreenPositionInput,
+            0x07 => FunctionCode::MiscSwitchInput,
+            0x10 => FunctionCode::CardSystem,
+            0x11 => FunctionCode::MedalHopper,
+            0x12 => FunctionCode::GeneralPurposeOutput,
+            0x13 => FunctionCode::AnalogOutput,
+            0x14 => FunctionCode::CharacterOutput,
+            0x15 => FunctionCode::Backup,
+            code => FunctionCode::Unknown(code),
+        }
+    }
+}
+
+/// A function, that an I/O board supports, with its three parameters. The meaning of the parameters depends on the code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Function {
+    pub code: FunctionCode,
+    pub params: [u8; 3],
+}
+
+/// An iterator over the functions of a [`command::FEATCHK`] response. Created by [`Functions::new`].
+///
+/// The data is a list of 4 byte entries: a function code and three parameters, terminated by a `0x00` code.
+/// The iteration stops at the terminator or at the end of the data. An incomplete trailing entry is ignored.
+#[derive(Debug, Clone)]
+pub struct Functions<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Functions<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+}
+
+impl Iterator for Functions<'_> {
+    type Item = Function;
+
+    fn next(&mut self) -> Option<Function> {
+        let (&[code, p1, p2, p3], rest) = self.data.split_first_chunk::<4>()?;
+        if code == 0x00 {
+            self.data = &[];
+            return None;
+        }
+
+        self.data = rest;
+        Some(Function {
+            code: code.into(),
+            params: [p1, p2, p3],
+        })
+    }
+}
+
+/// Identification of an I/O board, collected from the responses to [`command::IOIDENT`], [`command::CMDREV`],
+/// [`command::JVSREV`] and [`command::COMMVER`] requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoardInfo {
+    /// The identification string, e.g. `NAMCO LTD.;I/O PCB-1000;ver1.0;for domestic only,no analog input`.
+    pub id: String,
+    pub command_revision: Version,
+    pub jvs_revision: Version,
+    pub communication_version: Version,
+}
+
+impl BoardInfo {
+    /// Returns the fields of the identification string, that are separated by `;`.
+    pub fn id_fields(&self) -> impl Iterator<Item = &str> {
+        self.id.split(';').map(str::trim)
+    }
+
+    /// Returns the manufacturer, which is the first field of the identification string.
+    pub fn manufacturer(&self) -> &str {
+        self.id_fields().next().unwrap_or_default()
+    }
+}
+
 impl_required_packet_blocks!(ResponsePacket);
 
+/// The maximum length of a frame (258 bytes): the SYNC, DEST and SIZE bytes, followed by up to 255 bytes counted by SIZE, the last of which is SUM.
+///
+/// Packets backed by smaller arrays, including the default `[u8; 256]`, can't hold every valid frame.
+pub const MAX_FRAME_SIZE: usize = <RequestPacket as Packet>::SIZE_INDEX + u8::MAX as usize + 1;
+
+/// A request packet, that can hold any valid frame.
+pub type MaxRequestPacket = RequestPacket<[u8; MAX_FRAME_SIZE]>;
+
+/// A response packet, that can hold any valid frame.
+pub type MaxResponsePacket = ResponsePacket<[u8; MAX_FRAME_SIZE]>;
+
+#[cfg(feature = "defmt")]
+impl<S: AsRef<[u8]> + AsMut<[u8]>> defmt::Format for ResponsePacket<S> {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "ResponsePacket {{ dest: {=u8:#04x}, size: {=u8}, report: {=u8}, data: {=[u8]:02x}, sum: {=u8:#04x} }}",
+            self.dest(),
+            self.size(),
+            self.report_raw(),
+            self.data(),
+            self.checksum()
+        )
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::packet::strategy::valid_packet;
 
     const REQUEST_DATA: [u8; 6] = [0xE0, 0xFF, 0x03, 0x01, 0x02, 0x05];
     const RESPONSE_DATA: [u8; 7] = [0xE0, 0xFF, 0x04, 0x01, 0x01, 0x02, 0x07];
@@ -57,13 +607,13 @@ mod tests {
     // Request Packet tests
     #[test]
     fn test_request_packet_from_slice() {
-        let packet = RequestPacket::<256>::from_slice(&REQUEST_DATA);
+        let packet = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
         assert_eq!(REQUEST_DATA, packet.as_slice());
     }
 
     #[test]
     fn test_request_packet_access_methods() {
-        let packet = RequestPacket::<256>::from_slice(&REQUEST_DATA);
+        let packet = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
 
         assert_eq!(packet.sync(), REQUEST_DATA[0]);
         assert_eq!(packet.dest(), REQUEST_DATA[1]);
@@ -72,9 +622,249 @@ mod tests {
         assert_eq!(packet.checksum(), REQUEST_DATA[5]);
     }
 
+    #[test]
+    fn test_request_packet_data_len() {
+        let packet = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
+        assert_eq!(packet.data_len(), 2);
+        assert!(!packet.data_is_empty());
+
+        let packet = RequestPacket::<[u8; 256]>::from_slice(&[0xE0, 0xFF, 0x01, 0x00]);
+        assert_eq!(packet.data_len(), 0);
+        assert!(packet.data_is_empty());
+    }
+
+    #[test]
+    fn test_request_packet_set_data_iter() {
+        use crate::PacketError;
+        let mut from_iter = RequestPacket::<[u8; 16]>::new();
+        from_iter
+            .set_data_iter(0u8..5)
+            .unwrap()
+            .calculate_checksum();
+
+        let mut from_slice = RequestPacket::<[u8; 16]>::new();
+        from_slice.set_data(&[0, 1, 2, 3, 4]).calculate_checksum();
+        assert_eq!(from_iter.as_slice(), from_slice.as_slice());
+
+        assert_eq!(
+            from_iter.set_data_iter(0u8..20).unwrap_err(),
+            PacketError::BufferTooSmall {
+                len: 17,
+                capacity: 16
+            }
+        );
+        assert_eq!(from_iter.size(), from_slice.size());
+    }
+
+    #[test]
+    fn test_request_packet_fill_data() {
+        use crate::PacketError;
+        let mut packet = RequestPacket::<[u8; 32]>::new();
+        packet.fill_data(0xAA, 10).unwrap();
+        assert_eq!(packet.size(), 11);
+        assert_eq!(packet.data(), [0xAA; 10]);
+
+        assert_eq!(
+            packet.fill_data(0x55, 29).unwrap_err(),
+            PacketError::BufferTooSmall {
+                len: 33,
+                capacity: 32
+            }
+        );
+        assert_eq!(packet.data(), [0xAA; 10]);
+    }
+
+    #[test]
+    fn test_request_packet_from_wire() {
+        use crate::PacketError;
+        let wire = [0xE0, 0xFF, 0x03, 0xD0, 0xDF, 0x01, 0xE3];
+        let packet = RequestPacket::<[u8; 16]>::from_wire(&wire).unwrap();
+        assert_eq!(packet.data(), [0xE0, 0x01]);
+        assert_eq!(packet, [0xE0, 0xFF, 0x03, 0xE0, 0x01, 0xE3]);
+
+        assert_eq!(
+            RequestPacket::<[u8; 16]>::from_wire(&wire[1..]).unwrap_err(),
+            PacketError::InvalidSync(0xFF)
+        );
+        assert!(matches!(
+            RequestPacket::<[u8; 16]>::from_wire(&wire[..5]),
+            Err(PacketError::TruncatedFrame { .. })
+        ));
+
+        assert_eq!(
+            RequestPacket::<[u8; 16]>::from_wire(&[0xE0, 0x01, 0x00]).unwrap_err(),
+            PacketError::FrameTooShort { len: 3, min: 4 }
+        );
+        // SIZE 1 leaves no place for the REPORT byte of a response.
+        assert_eq!(
+            ResponsePacket::<[u8; 16]>::from_wire(&[0xE0, 0x00, 0x01, 0x01]).unwrap_err(),
+            PacketError::FrameTooShort { len: 4, min: 5 }
+        );
+    }
+
+    #[test]
+    fn test_request_packet_checksum_is_valid_for_wire() {
+        type Request = RequestPacket<[u8; 16]>;
+        // The data byte 0xE0 is escaped as D0 DF, the checksum is the sum of FF, 03, E0 and 01.
+        let wire = [0xE0, 0xFF, 0x03, 0xD0, 0xDF, 0x01, 0xE3];
+        assert!(Request::checksum_is_valid_for_wire(&wire));
+        assert!(Request::checksum_is_valid_for_wire(&REQUEST_DATA));
+
+        // The sum of the escaped bytes on the wire.
+        let wire_sum = [0xE0, 0xFF, 0x03, 0xD0, 0xDF, 0x01, 0xB2];
+        assert!(!Request::checksum_is_valid_for_wire(&wire_sum));
+        assert!(!Request::checksum_is_valid_for_wire(&wire[..6]));
+        assert!(!Request::checksum_is_valid_for_wire(&wire[1..]));
+
+        // The checksum 0xD0 is escaped as well.
+        let escaped_sum = [0xE0, 0x01, 0x02, 0xCD, 0xD0, 0xCF];
+        assert!(Request::checksum_is_valid_for_wire(&escaped_sum));
+    }
+
+    #[test]
+    fn test_packet_eq_bytes() {
+        let packet = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
+        assert_eq!(packet, REQUEST_DATA);
+        assert_eq!(packet, [0xE0, 0xFF, 0x03, 0x01, 0x02, 0x05]);
+        assert_eq!(packet, &REQUEST_DATA[..]);
+        assert_ne!(packet, REQUEST_DATA[..5]);
+        assert_ne!(packet, [0xE0, 0xFF, 0x03, 0x01, 0x02, 0x05, 0x00]);
+
+        let packet = ResponsePacket::<Vec<u8>>::from_storage(RESPONSE_DATA.to_vec());
+        assert_eq!(packet, RESPONSE_DATA);
+    }
+
+    #[test]
+    fn test_packet_header() {
+        let request = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
+        assert_eq!(
+            request.header().len(),
+            RequestPacket::<[u8; 256]>::DATA_BEGIN_INDEX
+        );
+        assert_eq!(request.header(), &REQUEST_DATA[..3]);
+
+        let response = ResponsePacket::<[u8; 256]>::from_slice(&RESPONSE_DATA);
+        assert_eq!(
+            response.header().len(),
+            ResponsePacket::<[u8; 256]>::DATA_BEGIN_INDEX
+        );
+        assert_eq!(response.header(), &RESPONSE_DATA[..4]);
+    }
+
+    #[test]
+    fn test_request_packet_truncate_data() {
+        let mut packet = RequestPacket::<[u8; 16]>::new();
+        packet.set_sync().set_dest(0x01).finalize(&[1, 2, 3, 4, 5]);
+        packet.truncate_data(2);
+        assert_eq!(packet.size(), 3);
+        assert_eq!(packet.data(), [1, 2]);
+
+        packet.truncate_data(10);
+        assert_eq!(packet.data(), [1, 2]);
+    }
+
+    #[test]
+    fn test_request_packet_extend_data() {
+        use crate::PacketError;
+        let mut packet = RequestPacket::<[u8; 16]>::new();
+        packet.set_sync().set_dest(0x01).set_data(&[]);
+        packet.extend_data(&[0x10, 0x11]).unwrap();
+        packet.extend_data(&[0x20, 0x02, 0x02]).unwrap();
+        assert_eq!(packet.size(), 6);
+        assert_eq!(packet.data(), [0x10, 0x11, 0x20, 0x02, 0x02]);
+
+        assert_eq!(
+            packet.extend_data(&[0; 8]).unwrap_err(),
+            PacketError::BufferTooSmall {
+                len: 17,
+                capacity: 16
+            }
+        );
+        assert_eq!(packet.data(), [0x10, 0x11, 0x20, 0x02, 0x02]);
+    }
+
+    #[test]
+    fn test_request_packet_data_u16() {
+        let mut packet = RequestPacket::<[u8; 16]>::new();
+        packet.set_sync().set_dest(0x01).set_data_u16_be(0, 0x0102);
+        assert_eq!(packet.data(), &[0x01, 0x02]);
+        assert_eq!(packet.data_u16_be(0), 0x0102);
+        assert_eq!(packet.data_u16_le(0), 0x0201);
+
+        packet.set_data_u16_le(2, 0x0304);
+        assert_eq!(packet.data(), &[0x01, 0x02, 0x04, 0x03]);
+        assert_eq!(packet.data_u16_le(2), 0x0304);
+
+        packet.set_data_u16_be(1, 0xAABB);
+        assert_eq!(packet.data(), &[0x01, 0xAA, 0xBB, 0x03]);
+    }
+
+    #[test]
+    fn test_request_packet_remaining_capacity() {
+        let mut packet = RequestPacket::<[u8; 16]>::new();
+        assert_eq!(packet.remaining_capacity(), 12);
+
+        packet.set_data(&[0x01, 0x02, 0x03, 0x04, 0x05]);
+        assert_eq!(packet.remaining_capacity(), 7);
+
+        packet.set_data(&[0x00; 12]);
+        assert_eq!(packet.remaining_capacity(), 0);
+
+        let packet = MaxRequestPacket::new();
+        assert_eq!(packet.remaining_capacity(), 254);
+    }
+
+    #[test]
+    fn test_request_packet_command() {
+        let mut packet = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
+        assert_eq!(packet.command(), REQUEST_DATA[3]);
+
+        packet.set_command(0x10);
+        assert_eq!(packet.command(), 0x10);
+        assert_eq!(packet.data(), &[0x10, REQUEST_DATA[4]]);
+
+        let mut packet = RequestPacket::<[u8; 256]>::new();
+        packet.set_sync().set_dest(0xFF).set_command(0x10);
+        assert_eq!(packet.data(), &[0x10]);
+    }
+
+    #[test]
+    fn test_request_packet_gpio_output() {
+        let packet = RequestPacket::<[u8; 256]>::gpio_output(0x01, &[0xFF, 0x00]);
+        assert_eq!(
+            packet.as_slice(),
+            [0xE0, 0x01, 0x05, 0x32, 0x02, 0xFF, 0x00, 0x39]
+        );
+    }
+
+    #[test]
+    fn test_request_packet_analog_output() {
+        let packet = RequestPacket::<[u8; 256]>::analog_output(0x01, &[0x1234, 0x5678]);
+        assert_eq!(
+            packet.as_slice(),
+            [0xE0, 0x01, 0x07, 0x33, 0x02, 0x12, 0x34, 0x56, 0x78, 0x51]
+        );
+    }
+
+    #[test]
+    fn test_request_packet_analog_input() {
+        let packet = RequestPacket::<[u8; 256]>::analog_input(0x01, 0x02);
+        assert_eq!(packet.as_slice(), [0xE0, 0x01, 0x03, 0x22, 0x02, 0x28]);
+    }
+
+    #[test]
+    fn test_request_packet_coin_decrement() {
+        let packet = RequestPacket::<[u8; 256]>::coin_decrement(0x01, 0x01, 0x0102);
+        assert_eq!(
+            packet.as_slice(),
+            [0xE0, 0x01, 0x05, 0x30, 0x01, 0x01, 0x02, 0x3A]
+        );
+        assert!(packet.verify_checksum());
+    }
+
     #[test]
     fn test_request_packet_setter_methods() {
-        let mut packet = RequestPacket::<256>::new();
+        let mut packet = RequestPacket::<[u8; 256]>::new();
         packet
             .set_sync()
             .set_dest(REQUEST_DATA[1])
@@ -89,31 +879,667 @@ mod tests {
         assert_eq!(packet.size(), REQUEST_DATA[2] - 1);
     }
 
+    #[test]
+    fn test_request_packet_checksum_range() {
+        let mut packet = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
+        assert_eq!(packet.checksum_range(), 1..5);
+
+        let summed = packet.as_ref()[packet.checksum_range()].to_vec();
+        packet.calculate_checksum_with(|bytes| {
+            assert_eq!(bytes, summed);
+            0x00
+        });
+        assert_eq!(packet.checksum(), 0x00);
+
+        let packet = RequestPacket::<[u8; 256]>::new();
+        assert!(packet.checksum_range().is_empty());
+    }
+
+    #[test]
+    fn test_request_packet_set_dest() {
+        let mut packet = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
+        packet.set_dest(0x01);
+        assert_eq!(packet.dest(), 0x01);
+        assert_eq!(packet.checksum(), REQUEST_DATA[5]);
+        assert!(!packet.verify_checksum());
+
+        packet.set_dest_and_checksum(0x02);
+        assert_eq!(packet.dest(), 0x02);
+        assert_eq!(packet.checksum(), 0x08);
+        assert!(packet.verify_checksum());
+    }
+
+    #[test]
+    fn test_request_packet_len_of_packet_clamped() {
+        let mut packet = RequestPacket::<[u8; 8]>::from_slice(&REQUEST_DATA);
+        packet.set_size(0xFF);
+
+        assert_eq!(packet.len_of_packet(), 8);
+        assert_eq!(packet.as_slice().len(), 8);
+        assert_eq!(packet.as_mut_slice().len(), 8);
+    }
+
+    #[test]
+    fn test_request_packet_checksum_degenerate() {
+        let mut packet = RequestPacket::<[u8; 256]>::new();
+        packet.set_sync().calculate_checksum();
+        assert_eq!(packet.size(), 0);
+        assert!(!packet.verify_checksum());
+
+        let mut packet = RequestPacket::<[u8; 8]>::new();
+        packet.set_sync().set_size(0xFF).calculate_checksum();
+        assert_eq!(packet.size(), 0xFF);
+        assert!(!packet.verify_checksum());
+
+        let mut packet = RequestPacket::<[u8; 256]>::new();
+        packet
+            .set_sync()
+            .set_dest(0x01)
+            .set_data(&[])
+            .calculate_checksum();
+        assert_eq!(packet.as_slice(), [0xE0, 0x01, 0x01, 0x02]);
+        assert!(packet.verify_checksum());
+    }
+
+    #[test]
+    fn test_request_packet_checksum_with() {
+        use crate::{Checksum, WrappingSum};
+        let mut packet = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
+        packet.set_checksum(0);
+        packet.calculate_checksum_with(WrappingSum::checksum);
+        assert_eq!(packet.checksum(), REQUEST_DATA[5]);
+
+        packet.calculate_checksum_with(|bytes| bytes.iter().fold(0, |acc, &x| acc ^ x));
+        assert_eq!(packet.checksum(), 0xFF ^ 0x03 ^ 0x01 ^ 0x02);
+    }
+
+    #[test]
+    fn test_checksum_state() {
+        use crate::ChecksumState;
+        let mut state = ChecksumState::new();
+        REQUEST_DATA[1..5].iter().for_each(|&b| state.update(b));
+
+        let mut packet = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
+        packet.set_checksum(0x00).calculate_checksum();
+        assert_eq!(state.finish(), packet.checksum());
+    }
+
+    #[test]
+    fn test_request_packet_debug() {
+        let packet = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
+        assert_eq!(
+            format!("{:?}", packet),
+            "RequestPacket { dest: 0xff, size: 3, data: [01, 02], sum: 0x05, frame: [e0, ff, 03, 01, 02, 05] }"
+        );
+
+        let packet = RequestPacket::<[u8; 256]>::new();
+        assert_eq!(
+            format!("{:?}", packet),
+            "RequestPacket { size: 0, frame: [00, 00, 00] }"
+        );
+    }
+
+    #[test]
+    fn test_request_packet_size_matches_data() {
+        let mut packet = RequestPacket::<[u8; 8]>::from_slice(&REQUEST_DATA);
+        assert!(packet.size_matches_data());
+
+        packet.set_size(0x06);
+        assert!(!packet.size_matches_data());
+        packet.set_size(0x00);
+        assert!(!packet.size_matches_data());
+    }
+
+    #[test]
+    fn test_request_packet_validate() {
+        use crate::PacketError;
+        let mut packet = RequestPacket::<[u8; 8]>::from_slice(&REQUEST_DATA);
+        assert_eq!(packet.validate(), Ok(()));
+
+        packet.set_checksum(0x00);
+        assert_eq!(
+            packet.validate(),
+            Err(PacketError::InvalidChecksum {
+                expected: 0x05,
+                got: 0x00
+            })
+        );
+
+        packet.set_size(0x10);
+        assert_eq!(
+            packet.validate(),
+            Err(PacketError::BufferTooSmall {
+                len: 19,
+                capacity: 8
+            })
+        );
+
+        packet.set_size(0x00);
+        assert_eq!(
+            packet.validate(),
+            Err(PacketError::FrameTooShort { len: 3, min: 4 })
+        );
+
+        packet.as_mut()[0] = 0x00;
+        assert_eq!(packet.validate(), Err(PacketError::InvalidSync(0x00)));
+    }
+
+    #[test]
+    fn test_request_packet_clear() {
+        let mut packet = RequestPacket::<[u8; 16]>::from_slice(&REQUEST_DATA);
+        packet.set_data(&[0x01, 0x02, 0x03, 0x04]).calculate_checksum();
+        packet.clear();
+
+        assert!(packet.as_ref().iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_request_packet_reset() {
+        let mut packet = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
+        packet.reset();
+
+        assert_eq!(packet.sync(), crate::SYNC_BYTE);
+        assert_eq!(packet.size(), 0x01);
+        assert_eq!(packet.dest(), 0x00);
+        assert!(packet.data_is_empty());
+        assert_eq!(packet.validate(), Ok(()));
+        assert_eq!(packet.as_ref()[4..6], REQUEST_DATA[4..6]);
+    }
+
+    #[test]
+    fn test_request_packet_read_until() {
+        use crate::{PacketError, ReadPacket};
+        let wire = [
+            0xE0, 0x01, 0x02, 0x10, 0x13, // from 0x01
+            0xE0, 0x02, 0x02, 0x10, 0x14, // from 0x02
+            0xE0, 0x03, 0x02, 0x10, 0x15, // from 0x03
+        ];
+        let mut packet = RequestPacket::<[u8; 16]>::new();
+
+        let mut cursor = std::io::Cursor::new(wire);
+        let len = cursor
+            .read_packet_until(&mut packet, |p| p.dest() == 0x03, 3)
+            .unwrap();
+        assert_eq!(len, 5);
+        assert_eq!(packet.as_slice(), &wire[10..]);
+
+        let mut cursor = std::io::Cursor::new(wire);
+        let err = cursor
+            .read_packet_until(&mut packet, |p| p.dest() == 0x03, 2)
+            .unwrap_err();
+        assert_eq!(
+            err.into_inner().unwrap().downcast_ref::<PacketError>(),
+            Some(&PacketError::NoMatchingFrame { frames: 2 })
+        );
+        assert_eq!(cursor.position(), 10);
+    }
+
+    #[test]
+    fn test_request_packet_read_bounded() {
+        use crate::{PacketError, ReadPacket};
+        // Every DATA byte is escaped, so 8 bytes of the frame take 12 bytes on the wire.
+        let wire = [
+            0xE0, 0x01, 0x05, 0xD0, 0xCF, 0xD0, 0xCF, 0xD0, 0xCF, 0xD0, 0xCF, 0x46,
+        ];
+        let mut packet = RequestPacket::<[u8; 16]>::new();
+
+        let mut cursor = std::io::Cursor::new(wire);
+        let err = cursor.read_packet_bounded(&mut packet, 11).unwrap_err();
+        assert_eq!(
+            err.into_inner().unwrap().downcast_ref::<PacketError>(),
+            Some(&PacketError::FrameTooLong { max: 11 })
+        );
+        assert_eq!(cursor.position(), 11);
+
+        let mut cursor = std::io::Cursor::new(wire);
+        assert_eq!(cursor.read_packet_bounded(&mut packet, 12).unwrap(), 8);
+        assert_eq!(packet.data(), [0xD0; 4]);
+        assert!(packet.verify_checksum());
+    }
+
+    #[test]
+    fn test_request_packet_new_boxed() {
+        use crate::ReadPacket;
+        let mut frame = vec![0xE0, 0x01, 0xFF];
+        frame.extend_from_slice(&[0x11; 0xFE]);
+        frame.push(0xDE);
+        let mut packet = RequestPacket::<[u8; 4096]>::new_boxed();
+        assert!(packet.inner.iter().all(|&b| b == 0));
+
+        std::io::Cursor::new(&frame)
+            .read_packet(&mut *packet)
+            .unwrap();
+        assert_eq!(packet.as_slice(), frame);
+        assert_eq!(packet.data_len(), 0xFE);
+        assert!(packet.verify_checksum());
+    }
+
     #[test]
     fn test_request_packet_read() {
         use crate::ReadPacket;
         let mut cursor = std::io::Cursor::new(REQUEST_DATA);
-        let mut packet = RequestPacket::<256>::new();
+        let mut packet = RequestPacket::<[u8; 256]>::new();
         cursor.read_packet(&mut packet).unwrap();
 
         assert_eq!(cursor.into_inner(), packet.as_slice())
     }
 
+    #[test]
+    fn test_request_packet_read_truncated() {
+        use crate::{PacketError, ReadPacket};
+        let mut reader = std::io::Cursor::new(&REQUEST_DATA[..4]);
+        let mut packet = RequestPacket::<[u8; 256]>::new();
+        let err = reader.read_packet(&mut packet).unwrap_err();
+
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+        assert_eq!(
+            err.into_inner().unwrap().downcast::<PacketError>().unwrap(),
+            Box::new(PacketError::TruncatedFrame { expected: 6, got: 4 })
+        );
+    }
+
+    #[test]
+    fn test_request_packet_try_read() {
+        use crate::{ReadPacket, ReadState};
+        let mut packet = RequestPacket::<[u8; 256]>::new();
+        let mut state = ReadState::new();
+
+        let mut reader = std::io::Cursor::new(&REQUEST_DATA[..4]);
+        assert_eq!(
+            reader.try_read_packet(&mut packet, &mut state).unwrap(),
+            None
+        );
+        assert_eq!(state.pos(), 4);
+
+        let mut reader = std::io::Cursor::new(&REQUEST_DATA[4..]);
+        assert_eq!(
+            reader.try_read_packet(&mut packet, &mut state).unwrap(),
+            Some(6)
+        );
+        assert_eq!(packet.as_slice(), REQUEST_DATA);
+        assert_eq!(state.pos(), 0);
+    }
+
+    #[test]
+    fn test_request_packet_read_escaped() {
+        use crate::ReadPacket;
+        // The body and the checksum contain escaped bytes, the last byte belongs to the next frame.
+        let frame = [0xE0, 0x01, 0x04, 0xE0, 0xD0, 0x2B, 0xE0];
+        let wire = [
+            0xE0, 0x01, 0x04, 0xD0, 0xDF, 0xD0, 0xCF, 0x2B, 0xD0, 0xDF, 0xAA,
+        ];
+        let mut cursor = std::io::Cursor::new(wire);
+        let mut packet = RequestPacket::<[u8; 256]>::new();
+        cursor.read_packet(&mut packet).unwrap();
+
+        assert_eq!(packet.as_slice(), frame);
+        assert!(packet.verify_checksum());
+        assert_eq!(cursor.position() as usize, wire.len() - 1);
+    }
+
+    #[test]
+    fn test_read_owned() {
+        use crate::ReadPacket;
+        let mut wire = REQUEST_DATA.to_vec();
+        wire.extend_from_slice(&RESPONSE_DATA);
+        let mut reader = std::io::Cursor::new(wire);
+
+        let request = reader.read_request::<16>().unwrap();
+        assert_eq!(request.as_slice(), REQUEST_DATA);
+        let response = reader.read_response::<16>().unwrap();
+        assert_eq!(response.as_slice(), RESPONSE_DATA);
+        assert!(reader.read_response::<16>().is_err());
+    }
+
     #[test]
     fn test_request_packet_write() {
         use crate::WritePacket;
         let mut writer = std::io::Cursor::new(vec![]);
-        let packet = RequestPacket::<256>::from_slice(&REQUEST_DATA);
+        let packet = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
         writer.write_packet_with_checksum(&packet).unwrap();
 
         assert_eq!(writer.into_inner(), packet.as_slice())
     }
 
+    #[test]
+    fn test_request_packet_write_escaped_checksum() {
+        use crate::WritePacket;
+        for (data, escaped_sum) in [([0xDC, 0x00], [0xD0, 0xDF]), ([0xCC, 0x00], [0xD0, 0xCF])] {
+            let mut packet = RequestPacket::<[u8; 16]>::new();
+            packet
+                .set_sync()
+                .set_dest(0x01)
+                .set_data(&data)
+                .calculate_checksum();
+            assert_eq!(packet.checksum(), escaped_sum[1] + 1);
+
+            let mut writer = std::io::Cursor::new(vec![]);
+            let written = writer.write_packet(&packet).unwrap();
+            let wire = writer.into_inner();
+            assert_eq!(wire[..5], packet.as_slice()[..5]);
+            assert_eq!(wire[5..], escaped_sum);
+            assert_eq!(written, 7);
+        }
+    }
+
+    #[test]
+    fn test_request_packet_write_packets() {
+        use crate::{ReadPacket, WritePacket};
+        let packets = [
+            RequestPacket::<[u8; 16]>::from_slice(&REQUEST_DATA),
+            RequestPacket::<[u8; 16]>::from_slice(&[0xE0, 0x01, 0x02, 0xE0, 0xE3]),
+        ];
+        let mut writer = std::io::Cursor::new(vec![]);
+        let written = writer.write_packets(&packets).unwrap();
+        assert_eq!(written, 12);
+
+        let mut reader = std::io::Cursor::new(writer.into_inner());
+        for packet in &packets {
+            let mut read = RequestPacket::<[u8; 16]>::new();
+            reader.read_packet(&mut read).unwrap();
+            assert_eq!(read.as_slice(), packet.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_request_packet_raw_round_trip() {
+        use crate::{ReadPacket, WritePacket};
+        let frame = [0xE0, 0xFF, 0x03, 0xE0, 0x01, 0xE3];
+        let packet = RequestPacket::<[u8; 16]>::from_slice(&frame);
+
+        let mut writer = std::io::Cursor::new(vec![]);
+        assert_eq!(writer.write_packet_raw(&packet).unwrap(), frame.len());
+        assert_eq!(writer.get_ref(), &frame);
+
+        let mut reader = std::io::Cursor::new(writer.into_inner());
+        let mut read = RequestPacket::<[u8; 16]>::new();
+        reader.read_packet_raw(&mut read).unwrap();
+        assert_eq!(read.as_slice(), frame);
+    }
+
+    #[test]
+    fn test_request_packet_reader() {
+        use crate::WritePacket;
+        let packet = RequestPacket::<[u8; 256]>::from_slice(&[0xE0, 0xFF, 0x03, 0xE0, 0xDD, 0x00]);
+        let mut written = vec![];
+        written.write_packet_with_checksum(&packet).unwrap();
+
+        let mut copied = vec![];
+        let len = std::io::copy(&mut packet.reader(), &mut copied).unwrap();
+        assert_eq!(copied, written);
+        assert_eq!(len as usize, packet.wire_len());
+
+        let mut one_by_one = vec![];
+        let mut reader = packet.reader();
+        let mut buf = [0];
+        while std::io::Read::read(&mut reader, &mut buf).unwrap() == 1 {
+            one_by_one.push(buf[0]);
+        }
+        assert_eq!(one_by_one, written);
+    }
+
+    #[test]
+    fn test_request_packet_as_bytes_escaped() {
+        use crate::WritePacket;
+        let packet = RequestPacket::<[u8; 256]>::from_slice(&[0xE0, 0xFF, 0x03, 0xE0, 0xDD, 0x00]);
+        let mut writer = std::io::Cursor::new(vec![]);
+        writer.write_packet_with_checksum(&packet).unwrap();
+
+        assert_eq!(packet.as_bytes_escaped(), writer.into_inner());
+    }
+
+    #[test]
+    fn test_request_packet_encode_into() {
+        use crate::PacketError;
+        let packet = RequestPacket::<[u8; 256]>::from_slice(&[0xE0, 0xFF, 0x03, 0xD0, 0x01, 0xD3]);
+        let wire = [0xE0, 0xFF, 0x03, 0xD0, 0xCF, 0x01, 0xD3];
+
+        let mut out = [0; 7];
+        assert_eq!(packet.encode_into(&mut out), Ok(wire.len()));
+        assert_eq!(out, wire);
+
+        let mut out = [0; 6];
+        assert_eq!(
+            packet.encode_into(&mut out),
+            Err(PacketError::BufferTooSmall {
+                len: 7,
+                capacity: 6
+            })
+        );
+        assert_eq!(out, [0; 6]);
+    }
+
+    #[test]
+    fn test_request_packet_decode_from() {
+        use crate::PacketError;
+        let wire = [0xE0, 0xFF, 0x03, 0xD0, 0xCF, 0x01, 0xD3, 0xE0, 0xFF];
+        let mut packet = RequestPacket::<[u8; 256]>::new();
+
+        assert_eq!(packet.decode_from(&wire), Ok(7));
+        assert_eq!(packet.as_slice(), [0xE0, 0xFF, 0x03, 0xD0, 0x01, 0xD3]);
+        assert_eq!(
+            packet.decode_from(&wire[..5]),
+            Err(PacketError::TruncatedFrame {
+                expected: 6,
+                got: 4
+            })
+        );
+        assert_eq!(
+            packet.decode_from(&wire[1..]),
+            Err(PacketError::InvalidSync(0xFF))
+        );
+    }
+
+    #[test]
+    fn test_request_packet_write_escaped_count() {
+        use crate::WritePacket;
+        let mut writer = std::io::Cursor::new(vec![]);
+        let packet = RequestPacket::<[u8; 256]>::from_slice(&[0xE0, 0xFF, 0x03, 0xD0, 0x01, 0xD3]);
+        let written = writer.write_packet_with_checksum(&packet).unwrap();
+
+        let wire = writer.into_inner();
+        assert_eq!(wire, [0xE0, 0xFF, 0x03, 0xD0, 0xCF, 0x01, 0xD3]);
+        assert_eq!(written, wire.len());
+    }
+
+    #[test]
+    fn test_request_packet_needs_escaping() {
+        let packet = RequestPacket::<[u8; 256]>::from_slice(&[0xE0, 0xFF, 0x03, 0xD0, 0x01, 0xD3]);
+        assert!(packet.needs_escaping());
+
+        let packet = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
+        assert!(!packet.needs_escaping());
+    }
+
+    #[test]
+    fn test_request_packet_wire_len() {
+        use crate::WritePacket;
+        let mut packet =
+            RequestPacket::<[u8; 256]>::from_slice(&[0xE0, 0xFF, 0x03, 0xE0, 0xD0, 0x00]);
+        let mut writer = std::io::Cursor::new(vec![]);
+        let written = writer.write_packet_with_checksum(&packet).unwrap();
+
+        assert_eq!(packet.len_of_packet(), 6);
+        assert_eq!(packet.wire_len(), 8);
+        assert_eq!(packet.wire_len(), written);
+
+        packet.set_data(&[0xDD, 0x01]);
+        assert_eq!(packet.wire_len(), 7);
+    }
+
+    #[test]
+    fn test_request_packet_write_checked() {
+        use crate::{PacketError, WritePacket};
+        let mut packet = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
+        let mut writer = std::io::Cursor::new(vec![]);
+        writer.write_packet_checked(&packet).unwrap();
+        assert_eq!(writer.get_ref(), &REQUEST_DATA);
+
+        packet.set_dest(0x01);
+        let mut writer = std::io::Cursor::new(vec![]);
+        let err = writer.write_packet_checked(&packet).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert_eq!(
+            err.into_inner().unwrap().downcast::<PacketError>().unwrap(),
+            Box::new(PacketError::InvalidChecksum {
+                expected: 0x07,
+                got: 0x05
+            })
+        );
+        assert!(writer.get_ref().is_empty());
+    }
+
+    #[test]
+    fn test_request_packet_write_ignores_stale_checksum() {
+        use crate::WritePacket;
+        let mut writer = std::io::Cursor::new(vec![]);
+        let mut packet = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
+        packet.set_checksum(0x42);
+        writer.write_packet_with_checksum(&packet).unwrap();
+
+        assert_eq!(writer.into_inner(), REQUEST_DATA);
+    }
+
+    #[test]
+    fn test_request_packet_fragment() {
+        let payload: Vec<u8> = (0..25).collect();
+
+        let packets = RequestPacket::<[u8; 16]>::fragment(0x01, &payload[..24]);
+        assert_eq!(packets.len(), 2);
+
+        let packets = RequestPacket::<[u8; 16]>::fragment(0x01, &payload);
+        assert_eq!(packets.len(), 3);
+
+        let mut reassembled = vec![];
+        for packet in &packets {
+            let mut copy = packet.clone();
+            assert_eq!(copy.calculate_checksum().checksum(), packet.checksum());
+            assert_eq!(packet.dest(), 0x01);
+            reassembled.extend_from_slice(packet.data());
+        }
+        assert_eq!(reassembled, payload);
+
+        let packets = RequestPacket::<[u8; 1024]>::fragment(0x01, &[0; 300]);
+        assert_eq!(packets[0].size(), 0xFF);
+        assert_eq!(packets[1].data_len(), 300 - 254);
+    }
+
+    #[test]
+    fn test_request_packet_vec_storage() {
+        use crate::ReadPacket;
+        let mut reader = std::io::Cursor::new(REQUEST_DATA);
+        let mut packet = RequestPacket::with_capacity(16);
+        reader.read_packet(&mut packet).unwrap();
+
+        assert_eq!(packet.as_slice(), REQUEST_DATA);
+        assert_eq!(packet.into_storage().len(), 16);
+    }
+
+    #[test]
+    fn test_request_packet_try_new() {
+        assert_eq!(RequestPacket::try_new(16).unwrap().into_storage().len(), 16);
+        assert!(RequestPacket::try_new(4).is_some());
+        assert!(RequestPacket::try_new(3).is_none());
+        assert!(ResponsePacket::try_new(4).is_none());
+
+        RequestPacket::with_capacity(4).reset();
+        assert!(RequestPacket::try_from_storage(vec![0xE0, 0x01]).is_none());
+        assert!(ResponsePacket::try_from_storage(vec![0xE0, 0x01, 0x02, 0x03]).is_none());
+        assert!(RequestPacket::try_from_storage(vec![0xE0, 0x01, 0x01, 0x02]).is_some());
+        assert!(
+            std::panic::catch_unwind(|| RequestPacket::from_storage(vec![0xE0, 0x01])).is_err()
+        );
+        assert!(std::panic::catch_unwind(|| RequestPacket::with_capacity(3)).is_err());
+    }
+
+    #[test]
+    fn test_request_packet_borrowed_storage() {
+        let mut buf = [0; 16];
+        let mut packet = RequestPacket::from_storage(&mut buf[..]);
+        packet
+            .set_sync()
+            .set_dest(REQUEST_DATA[1])
+            .set_data(&[REQUEST_DATA[3], REQUEST_DATA[4]])
+            .calculate_checksum();
+
+        assert_eq!(packet.as_slice(), REQUEST_DATA);
+        assert_eq!(buf[..REQUEST_DATA.len()], REQUEST_DATA);
+    }
+
+    #[test]
+    fn test_request_packet_try_from() {
+        use crate::PacketError;
+        let packet = RequestPacket::<[u8; 256]>::try_from(&REQUEST_DATA[..]).unwrap();
+        assert_eq!(packet.as_slice(), REQUEST_DATA);
+
+        let mut data = REQUEST_DATA;
+        data[0] = 0xFF;
+        assert_eq!(
+            RequestPacket::<[u8; 256]>::try_from(&data[..]).unwrap_err(),
+            PacketError::InvalidSync(0xFF)
+        );
+        assert_eq!(
+            RequestPacket::<[u8; 256]>::try_from(&REQUEST_DATA[..3]).unwrap_err(),
+            PacketError::FrameTooShort { len: 3, min: 4 }
+        );
+        assert_eq!(
+            RequestPacket::<[u8; 5]>::try_from(&REQUEST_DATA[..]).unwrap_err(),
+            PacketError::BufferTooSmall { len: 6, capacity: 5 }
+        );
+        assert_eq!(
+            RequestPacket::<[u8; 256]>::try_from(&[0xE0, 0x01, 0x09, 0x00][..]).unwrap_err(),
+            PacketError::TruncatedFrame {
+                expected: 12,
+                got: 4
+            }
+        );
+        assert_eq!(
+            RequestPacket::<[u8; 256]>::try_from(&[REQUEST_DATA.as_slice(), &[0x00]].concat()[..])
+                .unwrap_err(),
+            PacketError::TruncatedFrame {
+                expected: 6,
+                got: 7
+            }
+        );
+    }
+
+
+    #[test]
+    fn test_max_frame_size() {
+        use crate::{ReadPacket, WritePacket};
+        assert_eq!(MAX_FRAME_SIZE, 258);
+
+        let mut request = MaxRequestPacket::new();
+        request.set_sync().finalize(&[0xAA; 254]);
+        assert_eq!(request.size(), u8::MAX);
+        assert_eq!(request.as_slice().len(), MAX_FRAME_SIZE);
+        assert_eq!(request.validate(), Ok(()));
+
+        let mut wire = vec![];
+        wire.write_packet(&request).unwrap();
+        let mut packet = MaxRequestPacket::new();
+        let len = std::io::Cursor::new(wire).read_packet(&mut packet).unwrap();
+        assert_eq!(len, 258);
+        assert_eq!(packet.as_slice(), request.as_slice());
+    }
+
+    #[test]
+    fn test_packet_index_methods() {
+        let packet = RequestPacket::<[u8; 256]>::new();
+        assert_eq!(packet.size_index(), 2);
+        assert_eq!(packet.data_begin_index(), 3);
+        assert_eq!(packet.destination_index(), 1);
+
+        let packet = ResponsePacket::<[u8; 256]>::new();
+        assert_eq!(packet.size_index(), 2);
+        assert_eq!(packet.data_begin_index(), 4);
+        assert_eq!(packet.destination_index(), 1);
+    }
 
     // Response Packet tests
     #[test]
     fn test_response_packet_from_slice() {
-        let packet = ResponsePacket::<256>::from_slice(&REQUEST_DATA);
+        let packet = ResponsePacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
         assert_eq!(REQUEST_DATA, packet.as_slice());
     }
 
@@ -121,12 +1547,12 @@ mod tests {
     // #[should_panic]
     // fn test_response_packet_from_slice_panic() {
     //     let data = [0, 1, 2];
-    //     ResponsePacket::<256>::from_slice(&data);
+    //     ResponsePacket::<[u8; 256]>::from_slice(&data);
     // }
 
     #[test]
     fn test_response_packet_access_methods() {
-        let packet = dbg!(ResponsePacket::<256>::from_slice(&RESPONSE_DATA));
+        let packet = dbg!(ResponsePacket::<[u8; 256]>::from_slice(&RESPONSE_DATA));
 
         assert_eq!(packet.sync(), RESPONSE_DATA[0]);
         assert_eq!(packet.dest(), RESPONSE_DATA[1]);
@@ -136,9 +1562,73 @@ mod tests {
         assert_eq!(packet.checksum(), RESPONSE_DATA[6]);
     }
 
+    #[test]
+    fn test_response_packet_data_len() {
+        let packet = ResponsePacket::<[u8; 256]>::from_slice(&RESPONSE_DATA);
+        assert_eq!(packet.data_len(), 2);
+        assert!(!packet.data_is_empty());
+
+        let packet = ResponsePacket::<[u8; 256]>::from_slice(&[0xE0, 0xFF, 0x02, 0x01, 0x02]);
+        assert_eq!(packet.data_len(), 0);
+        assert!(packet.data_is_empty());
+    }
+
+    #[test]
+    fn test_response_packet_data_mut() {
+        let mut packet = ResponsePacket::<[u8; 256]>::from_slice(&RESPONSE_DATA);
+        assert_eq!(packet.data_mut().len(), 2);
+        packet.data_mut()[1] = 0x10;
+
+        assert_eq!(
+            packet.as_slice(),
+            [0xE0, 0xFF, 0x04, 0x01, 0x01, 0x10, 0x07]
+        );
+        packet.calculate_checksum();
+        assert!(packet.verify_checksum());
+    }
+
+    #[test]
+    fn test_response_packet_finalize() {
+        let mut packet = ResponsePacket::<[u8; 256]>::new();
+        packet
+            .set_sync()
+            .set_dest(RESPONSE_DATA[1])
+            .set_report(RESPONSE_DATA[3])
+            .finalize(&RESPONSE_DATA[4..6]);
+        assert_eq!(packet.as_slice(), RESPONSE_DATA);
+        assert!(packet.verify_checksum());
+
+        packet.data_mut()[0] = 0x10;
+        assert!(!packet.verify_checksum());
+        packet.recalculate();
+        assert!(packet.verify_checksum());
+        assert_eq!(packet.size(), RESPONSE_DATA[2]);
+    }
+
+    #[test]
+    fn test_response_packet_data_iter() {
+        let packet = ResponsePacket::<[u8; 256]>::from_slice(&RESPONSE_DATA);
+        let sum: u32 = packet.data_iter().map(u32::from).sum();
+
+        assert_eq!(packet.data_iter().count(), 2);
+        assert_eq!(sum, RESPONSE_DATA[4] as u32 + RESPONSE_DATA[5] as u32);
+    }
+
+    #[test]
+    fn test_response_packet_matches_address() {
+        let mut packet = ResponsePacket::<[u8; 256]>::from_slice(&RESPONSE_DATA);
+        packet.set_dest(0x01);
+        assert!(packet.matches_address(0x01));
+        assert!(!packet.matches_address(0x02));
+
+        packet.set_dest(crate::BROADCAST_ADDRESS);
+        assert!(packet.matches_address(0x01));
+        assert!(packet.matches_address(0x02));
+    }
+
     #[test]
     fn test_response_packet_setter_methods() {
-        let mut packet = ResponsePacket::<256>::new();
+        let mut packet = ResponsePacket::<[u8; 256]>::new();
         packet
             .set_sync()
             .set_dest(RESPONSE_DATA[1])
@@ -154,21 +1644,406 @@ mod tests {
         assert_eq!(packet.size(), RESPONSE_DATA[2] - 1);
     }
 
+    #[test]
+    fn test_response_packet_is_error() {
+        let mut packet = ResponsePacket::<[u8; 256]>::from_slice(&RESPONSE_DATA);
+        assert!(packet.is_ok());
+        assert!(!packet.is_error());
+
+        for report in [0x00, 0x02, 0x03, 0x04, 0x05, 0xFF] {
+            packet.set_report(report);
+            assert!(!packet.is_ok());
+            assert!(packet.is_error());
+        }
+    }
+
+    #[test]
+    fn test_response_packet_set_report_enum() {
+        use crate::Report;
+        let mut packet = ResponsePacket::<[u8; 256]>::from_slice(&RESPONSE_DATA);
+
+        packet.set_report(Report::Normal);
+        assert_eq!(packet.report_raw(), 0x01);
+        assert!(matches!(packet.report(), Report::Normal));
+        packet.set_report(Report::IncorrectDataSize);
+        assert_eq!(packet.report_raw(), 0x02);
+        assert!(matches!(packet.report(), Report::IncorrectDataSize));
+        packet.set_report(Report::InvalidData);
+        assert_eq!(packet.report_raw(), 0x03);
+        assert!(matches!(packet.report(), Report::InvalidData));
+        packet.set_report(Report::Busy);
+        assert_eq!(packet.report_raw(), 0x04);
+        assert!(matches!(packet.report(), Report::Busy));
+        packet.set_report(Report::Unknown);
+        assert_eq!(packet.report_raw(), 0x00);
+        assert!(matches!(packet.report(), Report::Unknown));
+    }
+
+    #[test]
+    fn test_response_packet_report_and_data_order() {
+        let data = [RESPONSE_DATA[4], RESPONSE_DATA[5]];
+        let mut report_first = ResponsePacket::<[u8; 256]>::new();
+        report_first
+            .set_sync()
+            .set_dest(RESPONSE_DATA[1])
+            .set_report(RESPONSE_DATA[3])
+            .set_data(&data)
+            .calculate_checksum();
+
+        let mut data_first = ResponsePacket::<[u8; 256]>::new();
+        data_first
+            .set_sync()
+            .set_dest(RESPONSE_DATA[1])
+            .set_data(&data)
+            .set_report(RESPONSE_DATA[3])
+            .calculate_checksum();
+
+        let mut combined = ResponsePacket::<[u8; 256]>::new();
+        combined
+            .set_sync()
+            .set_dest(RESPONSE_DATA[1])
+            .set_report_and_data(RESPONSE_DATA[3], &data)
+            .calculate_checksum();
+
+        assert_eq!(report_first.as_slice(), RESPONSE_DATA);
+        assert_eq!(data_first.as_slice(), RESPONSE_DATA);
+        assert_eq!(combined.as_slice(), RESPONSE_DATA);
+    }
+
+    #[test]
+    fn test_response_packet_copy_from() {
+        use crate::PacketError;
+        let large = ResponsePacket::<[u8; 256]>::from_slice(&RESPONSE_DATA);
+        let mut small = ResponsePacket::<[u8; 16]>::new();
+        small.copy_from(&large).unwrap();
+        assert_eq!(small.as_slice(), RESPONSE_DATA);
+
+        let mut large = ResponsePacket::<[u8; 256]>::new();
+        large.copy_from(&small).unwrap();
+        assert_eq!(large.as_slice(), RESPONSE_DATA);
+
+        large
+            .set_report_and_data(0x01, &[0; 16])
+            .calculate_checksum();
+        assert_eq!(
+            small.copy_from(&large),
+            Err(PacketError::BufferTooSmall {
+                len: 21,
+                capacity: 16
+            })
+        );
+        assert_eq!(small.as_slice(), RESPONSE_DATA);
+    }
+
+    #[test]
+    fn test_response_packet_garbage_size() {
+        let mut packet =
+            ResponsePacket::<[u8; 8]>::from_slice(&[0xE0, 0xFF, 0xF0, 0x01, 0x01, 0x02]);
+        assert_eq!(packet.len_of_packet(), 8);
+        assert_eq!(packet.data(), &[0x01, 0x02, 0x00]);
+        assert!(!packet.verify_checksum());
+
+        packet.set_size(0);
+        assert!(packet.data().is_empty());
+        assert!(packet.data_mut().is_empty());
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_packet_accessors_never_panic(bytes in proptest::collection::vec(proptest::num::u8::ANY, 0..64)) {
+            use crate::{ReadPacket, WritePacket};
+
+            // Storages too small for a minimal frame are rejected at construction.
+            let request = RequestPacket::try_from_storage(bytes.clone());
+            proptest::prop_assert_eq!(request.is_some(), bytes.len() > 3);
+            if let Some(mut request) = request {
+                let _ = (request.sync(), request.dest(), request.size());
+                let _ = (request.as_slice(), request.data(), request.checksum(), request.verify_checksum());
+                request.calculate_checksum();
+                let _ = std::io::sink().write_packet(&request);
+            }
+
+            let response = ResponsePacket::try_from_storage(bytes.clone());
+            proptest::prop_assert_eq!(response.is_some(), bytes.len() > 4);
+            if let Some(mut response) = response {
+                let _ = (response.sync(), response.dest(), response.size());
+                let _ = (response.as_slice(), response.data(), response.checksum(), response.report());
+                response.data_mut();
+                let _ = std::io::sink().write_packet_with_checksum(&response);
+            }
+
+            let _ = std::io::Cursor::new(&bytes).read_packet(&mut RequestPacket::<[u8; 8]>::new());
+            let _ = std::io::Cursor::new(&bytes).read_packet(&mut ResponsePacket::<[u8; 8]>::new());
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_packet_round_trip(
+            request in valid_packet::<RequestPacket<[u8; 64]>>(),
+            response in valid_packet::<ResponsePacket<[u8; 64]>>(),
+        ) {
+            use crate::WritePacket;
+
+            let mut wire = vec![];
+            wire.write_packet_with_checksum(&request).unwrap();
+            wire.write_packet_with_checksum(&response).unwrap();
+
+            let mut reader = std::io::Cursor::new(wire);
+            let read_request = RequestPacket::<[u8; 64]>::from_reader(&mut reader).unwrap();
+            let read_response = ResponsePacket::<[u8; 64]>::from_reader(&mut reader).unwrap();
+
+            proptest::prop_assert!(read_request.verify_checksum());
+            proptest::prop_assert_eq!(read_request.as_slice(), request.as_slice());
+            proptest::prop_assert!(read_response.verify_checksum());
+            proptest::prop_assert_eq!(read_response.as_slice(), response.as_slice());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary")]
+    fn test_packet_arbitrary() {
+        use arbitrary::{Arbitrary, Unstructured};
+        let entropy: Vec<u8> = (0..=255).cycle().take(1024).collect();
+        let mut u = Unstructured::new(&entropy);
+
+        for _ in 0..4 {
+            let request = RequestPacket::<[u8; 32]>::arbitrary(&mut u).unwrap();
+            assert_eq!(request.sync(), crate::SYNC_BYTE);
+            assert!(request.verify_checksum());
+
+            let response = ResponsePacket::<[u8; 32]>::arbitrary(&mut u).unwrap();
+            assert_eq!(response.sync(), crate::SYNC_BYTE);
+            assert!(response.verify_checksum());
+        }
+    }
+
     #[test]
     fn test_response_packet_read() {
         use crate::ReadPacket;
         let mut reader = std::io::Cursor::new(RESPONSE_DATA);
-        let mut packet = ResponsePacket::<256>::new();
+        let mut packet = ResponsePacket::<[u8; 256]>::new();
         reader.read_packet(&mut packet).unwrap();
 
         assert_eq!(reader.into_inner(), packet.as_slice())
     }
 
+    #[test]
+    fn test_response_packet_read_escaped_back_to_back() {
+        use crate::ReadPacket;
+        let frame = [0xE0, 0x00, 0x04, 0x01, 0xE0, 0x05, 0xEA];
+        let mut wire = vec![0xE0, 0x00, 0x04, 0x01, 0xD0, 0xDF, 0x05, 0xEA];
+        wire.extend_from_slice(&RESPONSE_DATA);
+        let mut cursor = std::io::Cursor::new(wire);
+
+        let mut packet = ResponsePacket::<[u8; 256]>::new();
+        cursor.read_packet(&mut packet).unwrap();
+        assert_eq!(packet.as_slice(), frame);
+        assert_eq!(packet.data(), &[0xE0, 0x05]);
+        assert_eq!(cursor.position(), 8);
+
+        cursor.read_packet(&mut packet).unwrap();
+        assert_eq!(packet.as_slice(), RESPONSE_DATA);
+    }
+
+    #[test]
+    #[cfg(feature = "bytes")]
+    fn test_request_packet_from_bytes() {
+        let wire = [0xE0, 0xFF, 0x03, 0xD0, 0xDF, 0x01, 0xE3];
+        let mut buf = bytes::BytesMut::from(&wire[..4]);
+        assert!(RequestPacket::<[u8; 16]>::from_bytes(&mut buf).is_none());
+        assert_eq!(buf.len(), 4);
+
+        buf.extend_from_slice(&wire[4..]);
+        buf.extend_from_slice(&REQUEST_DATA[..2]);
+        let packet = RequestPacket::<[u8; 16]>::from_bytes(&mut buf).unwrap();
+        assert_eq!(packet.as_slice(), [0xE0, 0xFF, 0x03, 0xE0, 0x01, 0xE3]);
+        assert_eq!(&buf[..], &REQUEST_DATA[..2]);
+    }
+
+    #[test]
+    fn test_response_packet_parse_identify() {
+        use crate::PacketError;
+        let id = b"NAMCO LTD.;I/O PCB-1000;ver1.0;for domestic only,no analog input";
+        let mut data = id.to_vec();
+        data.push(0x00);
+        let mut packet = ResponsePacket::<[u8; 256]>::new();
+        packet
+            .set_sync()
+            .set_dest(0x00)
+            .set_report(0x01)
+            .finalize(&data);
+
+        let info = BoardInfo {
+            id: packet.parse_identify().unwrap(),
+            command_revision: Version::from_bcd(0x13),
+            jvs_revision: Version::from_bcd(0x30),
+            communication_version: Version::from_bcd(0x10),
+        };
+        assert_eq!(info.id.as_bytes(), id);
+        assert_eq!(info.manufacturer(), "NAMCO LTD.");
+        assert_eq!(info.id_fields().nth(1), Some("I/O PCB-1000"));
+        assert_eq!(info.command_revision.to_string(), "1.3");
+
+        packet.finalize(id);
+        assert_eq!(packet.parse_identify(), Err(PacketError::MissingTerminator));
+        packet.finalize(&[b'I', b'O', 0x8A, 0x00]);
+        assert_eq!(
+            packet.parse_identify(),
+            Err(PacketError::NonAscii {
+                index: 2,
+                byte: 0x8A
+            })
+        );
+    }
+
+    #[test]
+    fn test_response_packet_parse_functions() {
+        let mut packet = ResponsePacket::<[u8; 256]>::new();
+        packet.set_sync().set_report(0x01).finalize(&[
+            0x01, 0x02, 0x0D, 0x00, // 2 players, 13 switches
+            0x02, 0x02, 0x00, 0x00, // 2 coin slots
+            0x20, 0x01, 0x02, 0x03, // undefined
+            0x00, 0x00, 0x00, 0x00, // terminator
+            0x03, 0x08, 0x0A, 0x00, // after the terminator
+        ]);
+
+        assert_eq!(
+            packet.parse_functions(),
+            [
+                Function {
+                    code: FunctionCode::SwitchInput,
+                    params: [0x02, 0x0D, 0x00]
+                },
+                Function {
+                    code: FunctionCode::CoinInput,
+                    params: [0x02, 0x00, 0x00]
+                },
+                Function {
+                    code: FunctionCode::Unknown(0x20),
+                    params: [0x01, 0x02, 0x03]
+                },
+            ]
+        );
+
+        packet.finalize(&[0x02, 0x01, 0x00, 0x00, 0x01, 0x02]);
+        assert_eq!(packet.parse_functions().len(), 1);
+    }
+
+    #[test]
+    fn test_response_packet_parse_switches() {
+        use crate::PacketError;
+        let mut packet = ResponsePacket::<[u8; 256]>::new();
+        // Test switch, player 1: start, up, push 1 and push 3, player 2: service, right and push 10.
+        packet
+            .set_sync()
+            .set_report(0x01)
+            .finalize(&[0x80, 0xA2, 0x80, 0x44, 0x01]);
+
+        let switches = packet.parse_switches(2, 2).unwrap();
+        assert!(switches.test());
+        assert!(!switches.tilt(1));
+        assert_eq!(switches.players.len(), 2);
+
+        let p1 = &switches.players[0];
+        assert!(p1.start() && p1.up() && p1.button(1) && p1.button(3));
+        assert!(!p1.service() && !p1.down() && !p1.button(2) && !p1.button(10));
+        let p2 = &switches.players[1];
+        assert!(p2.service() && p2.right() && p2.button(10));
+        assert!(!p2.start() && !p2.button(1) && !p2.button(11));
+        assert_eq!(p2.as_bytes(), [0x44, 0x01]);
+
+        assert_eq!(
+            packet.parse_switches(2, 3),
+            Err(PacketError::DataTooShort {
+                len: 5,
+                expected: 7
+            })
+        );
+        assert!(matches!(
+            packet.parse_switches(2, 0),
+            Err(PacketError::InvalidArgument(_))
+        ));
+        assert_eq!(packet.parse_switches(0, 0).unwrap().players, []);
+    }
+
+    #[test]
+    fn test_response_packet_parse_coins() {
+        use crate::PacketError;
+        let mut packet = ResponsePacket::<[u8; 256]>::new();
+        // Slot 1: normal, 0x0123 coins. Slot 2: jammed, 0x3FFF coins.
+        packet
+            .set_sync()
+            .set_report(0x01)
+            .finalize(&[0x01, 0x23, 0x7F, 0xFF]);
+
+        let coins = packet.parse_coins(2).unwrap();
+        assert_eq!(
+            coins.slots,
+            [
+                CoinSlot {
+                    condition: CoinCondition::Normal,
+                    count: 0x0123
+                },
+                CoinSlot {
+                    condition: CoinCondition::Jammed,
+                    count: 0x3FFF
+                },
+            ]
+        );
+
+        assert_eq!(
+            packet.parse_coins(3),
+            Err(PacketError::DataTooShort {
+                len: 4,
+                expected: 6
+            })
+        );
+    }
+
+    #[test]
+    fn test_response_packet_parse_analog() {
+        use crate::PacketError;
+        let mut packet = ResponsePacket::<[u8; 256]>::new();
+        // 10 bit readings 0x3FF and 0x201, left-justified.
+        packet
+            .set_sync()
+            .set_report(0x01)
+            .finalize(&[0xFF, 0xC0, 0x80, 0x40]);
+
+        assert_eq!(packet.parse_analog(2).unwrap(), [0xFFC0, 0x8040]);
+        assert_eq!(packet.parse_analog(2).unwrap()[1] >> 6, 0x201);
+        assert_eq!(
+            packet.parse_analog(3),
+            Err(PacketError::DataTooShort {
+                len: 4,
+                expected: 6
+            })
+        );
+    }
+
+    #[test]
+    fn test_response_packet_parse_version() {
+        use crate::PacketError;
+        let mut packet = ResponsePacket::<[u8; 16]>::new();
+        packet.set_sync().set_report(0x01).finalize(&[0x30]);
+        assert_eq!(packet.parse_version(), Ok(Version { major: 3, minor: 0 }));
+
+        packet.finalize(&[]);
+        assert_eq!(
+            packet.parse_version(),
+            Err(PacketError::DataTooShort {
+                len: 0,
+                expected: 1
+            })
+        );
+    }
+
     #[test]
     fn test_response_packet_write() {
         use crate::WritePacket;
         let mut writer = std::io::Cursor::new(vec![]);
-        let packet = ResponsePacket::<256>::from_slice(&RESPONSE_DATA);
+        let packet = ResponsePacket::<[u8; 256]>::from_slice(&RESPONSE_DATA);
         writer.write_packet_with_checksum(&packet).unwrap();
 
         assert_eq!(writer.into_inner(), packet.as_slice())
@@ -15,7 +15,9 @@
 //! [REPORT]: crate::Report
 
 
-use crate::{impl_required_packet_blocks, Packet, ReportField};
+use std::io::{self, Read, Write};
+
+use crate::{impl_required_packet_blocks, FrameKind, Packet, ReadPacket, ReportField, WritePacket};
 
 pub trait ModifiedPacket: Packet {
     const CMD_INDEX: usize;
@@ -42,49 +44,135 @@ pub trait ModifiedPacket: Packet {
         self.as_mut()[Self::SEQUENCE_INDEX] = sequence;
         self
     }
+
+    /// Increments a SEQ byte at [`ModifiedPacket::SEQUENCE_INDEX`], wrapping from `0xFF` to `0x00`, and returns the new value.
+    ///
+    /// The checksum is not recalculated.
+    fn next_sequence(&mut self) -> u8 {
+        let sequence = self.sequence().wrapping_add(1);
+        self.set_sequence(sequence);
+        sequence
+    }
 }
 
+/// A wrapper, that compares packets by their SEQ byte only, e.g. for keeping in-flight requests in a `BTreeSet`.
+///
+/// The order is the plain numeric order of the SEQ byte, so `0x00` comes before `0xFF` even after the sequence wrapped around.
+/// Handling the wraparound is left to the caller. Packets with the same SEQ byte are equal, whatever the rest of the frame is.
 #[derive(Debug, Clone)]
-pub struct RequestPacket<const N: usize = 256> {
-    inner: [u8; N],
+pub struct BySequence<P>(pub P);
+
+impl<P: ModifiedPacket> PartialEq for BySequence<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.sequence() == other.0.sequence()
+    }
+}
+
+impl<P: ModifiedPacket> Eq for BySequence<P> {}
+
+impl<P: ModifiedPacket> PartialOrd for BySequence<P> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<P: ModifiedPacket> Ord for BySequence<P> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.sequence().cmp(&other.0.sequence())
+    }
+}
+
+#[derive(Clone)]
+pub struct RequestPacket<S = [u8; 256]> {
+    inner: S,
 }
 
-impl<const N: usize> Packet for RequestPacket<N> {
+impl<S: AsRef<[u8]> + AsMut<[u8]>> Packet for RequestPacket<S> {
     const DATA_BEGIN_INDEX: usize = 5;
     const SIZE_INDEX: usize = 1;
     const DESTINATION_INDEX: usize = 2;
+    const KIND: FrameKind = FrameKind::Request;
 }
 
-impl<const N: usize> ModifiedPacket for RequestPacket<N> {
+impl<S: AsRef<[u8]> + AsMut<[u8]>> ModifiedPacket for RequestPacket<S> {
     const CMD_INDEX: usize = 4;
     const SEQUENCE_INDEX: usize = 3;
 }
 
 impl_required_packet_blocks!(RequestPacket);
 
-#[derive(Debug, Clone)]
-pub struct ResponsePacket<const N: usize = 256> {
-    inner: [u8; N],
+#[cfg(feature = "defmt")]
+impl<S: AsRef<[u8]> + AsMut<[u8]>> defmt::Format for RequestPacket<S> {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "RequestPacket {{ size: {=u8}, dest: {=u8:#04x}, seq: {=u8}, cmd: {=u8:#04x}, data: {=[u8]:02x}, sum: {=u8:#04x} }}",
+            self.size(),
+            self.dest(),
+            self.sequence(),
+            self.cmd(),
+            self.data(),
+            self.checksum()
+        )
+    }
+}
+
+#[cfg(feature = "bitflags")]
+bitflags::bitflags! {
+    /// Condition bits of the STATUS byte of a response, for implementations, that report several conditions at once.
+    ///
+    /// Devices, that use plain status codes instead of bits, should be handled with [`ResponsePacket::status`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct StatusFlags: u8 {
+        /// The request was processed.
+        const NORMAL = 0x01;
+        /// The command is not supported by the device.
+        const UNSUPPORTED_COMMAND = 0x02;
+        /// The checksum of the request was invalid.
+        const CHECKSUM_ERROR = 0x04;
+        /// The device buffer overflowed.
+        const OVERFLOW = 0x08;
+    }
+}
+
+#[derive(Clone)]
+pub struct ResponsePacket<S = [u8; 256]> {
+    inner: S,
 }
 
-impl<const N: usize> Packet for ResponsePacket<N> {
+impl<S: AsRef<[u8]> + AsMut<[u8]>> Packet for ResponsePacket<S> {
     const DATA_BEGIN_INDEX: usize = 7;
     const SIZE_INDEX: usize = 1;
     const DESTINATION_INDEX: usize = 2;
+    const KIND: FrameKind = FrameKind::Response;
 }
 
-impl<const N: usize> ModifiedPacket for ResponsePacket<N> {
+impl<S: AsRef<[u8]> + AsMut<[u8]>> ModifiedPacket for ResponsePacket<S> {
     const CMD_INDEX: usize = 5;
     const SEQUENCE_INDEX: usize = 3;
 }
 
-impl<const N: usize> ReportField for ResponsePacket<N> {
+impl<S: AsRef<[u8]> + AsMut<[u8]>> ReportField for ResponsePacket<S> {
     const REPORT_INDEX: usize = 6;
 }
 
-impl<const N: usize> ResponsePacket<N> {
+impl<S: AsRef<[u8]> + AsMut<[u8]>> ResponsePacket<S> {
     const STATUS_INDEX: usize = 4;
 
+    /// Returns the address of the node, that sent the response.
+    ///
+    /// The frame has no separate source field: slaves echo their own address in the DEST byte,
+    /// so this is the same as [`Packet::dest`], named after what the byte means in a response.
+    #[doc(alias = "source")]
+    pub fn responder(&self) -> u8 {
+        self.dest()
+    }
+
+    /// Returns `true` if the response echoes the SEQ and CMD bytes of `request`, i.e. it answers that request.
+    pub fn responds_to(&self, request: &impl ModifiedPacket) -> bool {
+        self.sequence() == request.sequence() && self.cmd() == request.cmd()
+    }
+
     pub fn status(&self) -> u8 {
         self.as_ref()[Self::STATUS_INDEX]
     }
@@ -93,13 +181,134 @@ impl<const N: usize> ResponsePacket<N> {
         self.as_mut()[Self::STATUS_INDEX] = status;
         self
     }
+
+    /// Returns the STATUS byte as [`StatusFlags`]. Unknown bits are kept.
+    #[cfg(feature = "bitflags")]
+    pub fn status_flags(&self) -> StatusFlags {
+        StatusFlags::from_bits_retain(self.status())
+    }
+
+    /// Sets the STATUS byte from [`StatusFlags`].
+    #[cfg(feature = "bitflags")]
+    pub fn set_status_flags(&mut self, flags: StatusFlags) -> &mut Self {
+        self.set_status(flags.bits())
+    }
+}
+
+impl<const N: usize> ResponsePacket<[u8; N]> {
+    /// Creates a response to `request`, that echoes its DEST, SEQ and CMD bytes.
+    ///
+    /// The SYNC byte is set and the SIZE byte counts no data. STATUS, REPORT, data and the checksum are left for the caller,
+    /// e.g. `ResponsePacket::from_request(&request).set_status(0x01).set_report(Report::Normal).finalize(&data)`.
+    pub fn from_request<S: AsRef<[u8]> + AsMut<[u8]>>(request: &RequestPacket<S>) -> Self {
+        let mut response = Self::new();
+        response
+            .set_sync()
+            .set_dest(request.dest())
+            .set_sequence(request.sequence())
+            .set_cmd(request.cmd())
+            .set_data(&[]);
+        response
+    }
 }
 
 impl_required_packet_blocks!(ResponsePacket);
 
+/// Writes `request` with a freshly calculated checksum, then reads and checks the response to it.
+///
+/// A response with a non-normal report is still returned, so the caller can inspect it, e.g. with [`ReportField::report`].
+///
+/// # Errors
+/// - Errors of [`WritePacket::write_packet_with_checksum`] and [`ReadPacket::read_packet`].
+/// - [`io::ErrorKind::InvalidData`] wrapping an error of [`Packet::validate`] if the response is malformed or has an invalid checksum.
+/// - [`io::ErrorKind::InvalidData`] if the response doesn't echo the SEQ and CMD bytes of the request. See [`ResponsePacket::responds_to`].
+pub fn transact<T: Read + Write, const N: usize>(
+    io: &mut T,
+    request: &RequestPacket<[u8; N]>,
+) -> io::Result<ResponsePacket<[u8; N]>> {
+    io.write_packet_with_checksum(request)?;
+    io.flush()?;
+
+    let mut response = ResponsePacket::new();
+    io.read_packet(&mut response)?;
+    response.validate()?;
+    if !response.responds_to(request) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "The response (SEQ {:#04x}, CMD {:#04x}) doesn't answer the request (SEQ {:#04x}, CMD {:#04x})",
+                response.sequence(),
+                response.cmd(),
+                request.sequence(),
+                request.cmd()
+            ),
+        ));
+    }
+
+    Ok(response)
+}
+
+/// Like [`transact`], but sends the request again while the response report is [`Report::Busy`](crate::Report::Busy).
+///
+/// The request is sent at most `retries + 1` times. Before each retry `delay` is called with the number of the retry,
+/// starting from 1, so the caller can sleep or back off, e.g. `|n| std::thread::sleep(Duration::from_millis(10 << n))`.
+/// Responses with other reports are returned without retrying.
+///
+/// # Errors
+/// - Errors of [`transact`], which are not retried.
+/// - An error wrapping [`PacketError::TooManyRetries`](crate::PacketError::TooManyRetries) if the last response is still busy.
+pub fn transact_retry<T: Read + Write, const N: usize>(
+    io: &mut T,
+    request: &RequestPacket<[u8; N]>,
+    retries: usize,
+    mut delay: impl FnMut(usize),
+) -> io::Result<ResponsePacket<[u8; N]>> {
+    for retry in 0..=retries {
+        if retry > 0 {
+            delay(retry);
+        }
+        let response = transact(io, request)?;
+        if !matches!(response.report(), crate::Report::Busy) {
+            return Ok(response);
+        }
+    }
+
+    Err(crate::PacketError::TooManyRetries { retries }.into())
+}
+
+/// The maximum length of a frame (257 bytes): the SYNC and SIZE bytes, followed by up to 255 bytes counted by SIZE, the last of which is SUM.
+///
+/// Packets backed by smaller arrays, including the default `[u8; 256]`, can't hold every valid frame.
+pub const MAX_FRAME_SIZE: usize = <RequestPacket as Packet>::SIZE_INDEX + u8::MAX as usize + 1;
+
+/// A request packet, that can hold any valid frame.
+pub type MaxRequestPacket = RequestPacket<[u8; MAX_FRAME_SIZE]>;
+
+/// A response packet, that can hold any valid frame.
+pub type MaxResponsePacket = ResponsePacket<[u8; MAX_FRAME_SIZE]>;
+
+#[cfg(feature = "defmt")]
+impl<S: AsRef<[u8]> + AsMut<[u8]>> defmt::Format for ResponsePacket<S> {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "ResponsePacket {{ size: {=u8}, dest: {=u8:#04x}, seq: {=u8}, status: {=u8:#04x}, cmd: {=u8:#04x}, report: {=u8}, data: {=[u8]:02x}, sum: {=u8:#04x} }}",
+            self.size(),
+            self.dest(),
+            self.sequence(),
+            self.status(),
+            self.cmd(),
+            self.report_raw(),
+            self.data(),
+            self.checksum()
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::packet::strategy::valid_packet;
 
     const REQUEST_DATA: [u8; 8] = [0xE0, 0x06, 0xFF, 0x01, 0x02, 0x01, 0x02, 0x0B];
     const RESPONSE_DATA: [u8; 10] = [0xE0, 0x08, 0xFF, 0x01, 0x03, 0x02, 0x04, 0x01, 0x02, 0x14];
@@ -107,13 +316,13 @@ mod tests {
     // Request Packet tests
     #[test]
     fn test_request_packet_from_slice() {
-        let packet = RequestPacket::<256>::from_slice(&REQUEST_DATA);
+        let packet = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
         assert_eq!(REQUEST_DATA, packet.as_slice());
     }
 
     #[test]
     fn test_request_packet_access_methods() {
-        let packet = dbg!(RequestPacket::<256>::from_slice(&REQUEST_DATA));
+        let packet = dbg!(RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA));
 
         assert_eq!(packet.sync(), REQUEST_DATA[0]);
         assert_eq!(packet.size(), REQUEST_DATA[1]);
@@ -126,7 +335,7 @@ mod tests {
 
     #[test]
     fn test_request_packet_setter_methods() {
-        let mut packet = RequestPacket::<256>::new();
+        let mut packet = RequestPacket::<[u8; 256]>::new();
         packet
             .set_sync()
             .set_dest(REQUEST_DATA[2])
@@ -143,31 +352,113 @@ mod tests {
         assert_eq!(packet.size(), REQUEST_DATA[1] - 1);
     }
 
+    #[test]
+    fn test_request_packet_next_sequence() {
+        let mut packet = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
+        packet.set_sequence(0);
+        for i in 1..=300u32 {
+            assert_eq!(packet.next_sequence(), i as u8);
+        }
+        assert_eq!(packet.sequence(), (300 % 256) as u8);
+        assert_eq!(packet.cmd(), REQUEST_DATA[4]);
+    }
+
     #[test]
     fn test_request_packet_read() {
         use crate::ReadPacket;
         let mut cursor = std::io::Cursor::new(REQUEST_DATA);
-        let mut packet = RequestPacket::<256>::new();
+        let mut packet = RequestPacket::<[u8; 256]>::new();
         cursor.read_packet(&mut packet).unwrap();
 
         assert_eq!(cursor.into_inner(), packet.as_slice())
     }
 
+    #[test]
+    fn test_request_packet_read_escaped() {
+        use crate::ReadPacket;
+        // The body and the checksum contain escaped bytes, the last byte belongs to the next frame.
+        let frame = [0xE0, 0x06, 0x01, 0x02, 0xE0, 0xD0, 0x27, 0xE0];
+        let wire = [
+            0xE0, 0x06, 0x01, 0x02, 0xD0, 0xDF, 0xD0, 0xCF, 0x27, 0xD0, 0xDF, 0xAA,
+        ];
+        let mut cursor = std::io::Cursor::new(wire);
+        let mut packet = RequestPacket::<[u8; 256]>::new();
+        cursor.read_packet(&mut packet).unwrap();
+
+        assert_eq!(packet.as_slice(), frame);
+        assert!(packet.verify_checksum());
+        assert_eq!(cursor.position() as usize, wire.len() - 1);
+    }
+
+    #[test]
+    fn test_read_owned() {
+        use crate::ReadPacket;
+        let mut wire = REQUEST_DATA.to_vec();
+        wire.extend_from_slice(&RESPONSE_DATA);
+        let mut reader = std::io::Cursor::new(wire);
+
+        let request = reader.read_modified_request::<16>().unwrap();
+        assert_eq!(request.as_slice(), REQUEST_DATA);
+        let response = reader.read_modified_response::<16>().unwrap();
+        assert_eq!(response.as_slice(), RESPONSE_DATA);
+        assert!(reader.read_modified_response::<16>().is_err());
+    }
+
     #[test]
     fn test_request_packet_write() {
         use crate::WritePacket;
         let mut writer = std::io::Cursor::new(vec![]);
-        let packet = RequestPacket::<256>::from_slice(&REQUEST_DATA);
+        let packet = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
         writer.write_packet_with_checksum(&packet).unwrap();
 
         assert_eq!(writer.into_inner(), packet.as_slice())
     }
 
 
+    #[test]
+    fn test_max_frame_size() {
+        assert_eq!(MAX_FRAME_SIZE, 257);
+
+        let mut request = MaxRequestPacket::new();
+        request.set_sync().finalize(&[0xAA; 251]);
+        assert_eq!(request.size(), u8::MAX);
+        assert_eq!(request.as_slice().len(), MAX_FRAME_SIZE);
+        assert_eq!(request.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_response_packet_validate_header() {
+        use crate::PacketError;
+        // SIZE covers DEST, SEQ, STATUS and SUM only, so CMD and REPORT are missing.
+        let mut packet =
+            ResponsePacket::<[u8; 256]>::from_slice(&[0xE0, 0x04, 0xFF, 0x01, 0x03, 0x03]);
+        assert!(!packet.size_matches_data());
+        assert_eq!(
+            packet.validate(),
+            Err(PacketError::FrameTooShort { len: 6, min: 8 })
+        );
+
+        packet.set_size(0x06).calculate_checksum();
+        assert_eq!(packet.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_packet_index_methods() {
+        let packet = RequestPacket::<[u8; 256]>::new();
+        assert_eq!(packet.size_index(), 1);
+        assert_eq!(packet.data_begin_index(), 5);
+        assert_eq!(packet.destination_index(), 2);
+
+        let packet = ResponsePacket::<[u8; 256]>::new();
+        assert_eq!(packet.size_index(), 1);
+        assert_eq!(packet.data_begin_index(), 7);
+        assert_eq!(packet.destination_index(), 2);
+    }
+
     // Response Packet tests
     #[test]
     fn test_response_packet_from_slice() {
-        let packet = ResponsePacket::<256>::from_slice(&REQUEST_DATA);
+        let packet = ResponsePacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
         assert_eq!(REQUEST_DATA, packet.as_slice());
     }
 
@@ -175,12 +466,12 @@ mod tests {
     // #[should_panic]
     // fn test_response_packet_from_slice_panic() {
     //     let data = [0, 1, 2];
-    //     ResponsePacket::<256>::from_slice(&data);
+    //     ResponsePacket::<[u8; 256]>::from_slice(&data);
     // }
 
     #[test]
     fn test_response_packet_access_methods() {
-        let packet = ResponsePacket::<256>::from_slice(&RESPONSE_DATA);
+        let packet = ResponsePacket::<[u8; 256]>::from_slice(&RESPONSE_DATA);
 
         assert_eq!(packet.sync(), RESPONSE_DATA[0]);
         assert_eq!(packet.size(), RESPONSE_DATA[1]);
@@ -195,7 +486,7 @@ mod tests {
 
     #[test]
     fn test_response_packet_setter_methods() {
-        let mut packet = ResponsePacket::<256>::new();
+        let mut packet = ResponsePacket::<[u8; 256]>::new();
         packet
             .set_sync()
             .set_dest(RESPONSE_DATA[2])
@@ -214,11 +505,285 @@ mod tests {
         assert_eq!(packet.size(), RESPONSE_DATA[1] - 1);
     }
 
+    proptest::proptest! {
+        #[test]
+        fn test_packet_accessors_never_panic(bytes in proptest::collection::vec(proptest::num::u8::ANY, 0..64)) {
+            use crate::ReadPacket;
+
+            // Storages too small for a minimal frame are rejected at construction.
+            let request = RequestPacket::try_from_storage(bytes.clone());
+            proptest::prop_assert_eq!(request.is_some(), bytes.len() > 5);
+            if let Some(mut request) = request {
+                let _ = (request.sync(), request.dest(), request.size());
+                let _ = (request.as_slice(), request.data(), request.checksum(), request.sequence());
+                request.calculate_checksum();
+            }
+
+            let response = ResponsePacket::try_from_storage(bytes.clone());
+            proptest::prop_assert_eq!(response.is_some(), bytes.len() > 7);
+            if let Some(response) = response {
+                let _ = (response.sync(), response.dest(), response.size());
+                let _ = (response.as_slice(), response.data(), response.checksum(), response.status());
+            }
+
+            let _ = std::io::Cursor::new(&bytes).read_packet(&mut ResponsePacket::<[u8; 8]>::new());
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_packet_round_trip(
+            request in valid_packet::<RequestPacket<[u8; 64]>>(),
+            response in valid_packet::<ResponsePacket<[u8; 64]>>(),
+        ) {
+            use crate::WritePacket;
+
+            let mut wire = vec![];
+            wire.write_packet_with_checksum(&request).unwrap();
+            wire.write_packet_with_checksum(&response).unwrap();
+
+            let mut reader = std::io::Cursor::new(wire);
+            let read_request = RequestPacket::<[u8; 64]>::from_reader(&mut reader).unwrap();
+            let read_response = ResponsePacket::<[u8; 64]>::from_reader(&mut reader).unwrap();
+
+            proptest::prop_assert!(read_request.verify_checksum());
+            proptest::prop_assert_eq!(read_request.as_slice(), request.as_slice());
+            proptest::prop_assert!(read_response.verify_checksum());
+            proptest::prop_assert_eq!(read_response.as_slice(), response.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_response_packet_responder() {
+        let packet = ResponsePacket::<[u8; 256]>::from_slice(&RESPONSE_DATA);
+        assert_eq!(packet.responder(), RESPONSE_DATA[2]);
+        assert_eq!(packet.responder(), packet.dest());
+    }
+
+    #[test]
+    fn test_request_packet_by_sequence() {
+        let mut in_flight = std::collections::BTreeSet::new();
+        for sequence in [0x10, 0x02, 0xF0] {
+            let mut packet = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
+            packet.set_sequence(sequence);
+            in_flight.insert(BySequence(packet));
+        }
+
+        let sequences: Vec<u8> = in_flight.iter().map(|p| p.0.sequence()).collect();
+        assert_eq!(sequences, [0x02, 0x10, 0xF0]);
+    }
+
+    /// An IO, that collects written bytes and replies with canned bytes.
+    struct Loopback {
+        written: Vec<u8>,
+        replies: std::io::Cursor<Vec<u8>>,
+    }
+
+    impl Loopback {
+        fn new(replies: &[u8]) -> Self {
+            Self {
+                written: vec![],
+                replies: std::io::Cursor::new(replies.to_vec()),
+            }
+        }
+    }
+
+    impl Read for Loopback {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.replies.read(buf)
+        }
+    }
+
+    impl Write for Loopback {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.written.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_packet_header() {
+        let request = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
+        assert_eq!(
+            request.header().len(),
+            RequestPacket::<[u8; 256]>::DATA_BEGIN_INDEX
+        );
+        assert_eq!(request.header(), &REQUEST_DATA[..5]);
+
+        let response = ResponsePacket::<[u8; 256]>::from_slice(&RESPONSE_DATA);
+        assert_eq!(
+            response.header().len(),
+            ResponsePacket::<[u8; 256]>::DATA_BEGIN_INDEX
+        );
+        assert_eq!(response.header(), &RESPONSE_DATA[..7]);
+    }
+
+    #[test]
+    fn test_response_packet_assemble() {
+        use crate::PacketError;
+        let (header, data) = RESPONSE_DATA.split_at(7);
+        let response = ResponsePacket::<[u8; 256]>::assemble(header, &data[..2]).unwrap();
+        assert_eq!(response.as_slice(), RESPONSE_DATA);
+
+        assert_eq!(
+            ResponsePacket::<[u8; 256]>::assemble(&header[..6], &data[..2]).unwrap_err(),
+            PacketError::InvalidHeaderLength {
+                len: 6,
+                expected: 7
+            }
+        );
+        assert!(matches!(
+            ResponsePacket::<[u8; 16]>::assemble(header, &[0; 16]),
+            Err(PacketError::BufferTooSmall { .. })
+        ));
+    }
+
+    #[test]
+    fn test_response_packet_data_u32() {
+        let mut packet = ResponsePacket::<[u8; 32]>::from_slice(&RESPONSE_DATA);
+        packet.set_data(&[0x01]).set_data_u32_be(1, 0x0403_2A1F);
+        assert_eq!(packet.data(), &[0x01, 0x04, 0x03, 0x2A, 0x1F]);
+        assert_eq!(packet.data_u32_be(1), 0x0403_2A1F);
+        assert_eq!(packet.data_u32_le(1), 0x1F2A_0304);
+
+        packet.set_data_u32_le(3, 0xDEAD_BEEF);
+        assert_eq!(packet.data(), &[0x01, 0x04, 0x03, 0xEF, 0xBE, 0xAD, 0xDE]);
+        assert_eq!(packet.data_u32_le(3), 0xDEAD_BEEF);
+    }
+
+    #[test]
+    fn test_transact() {
+        let mut request = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
+        request.set_checksum(0x00);
+        let mut io = Loopback::new(&RESPONSE_DATA);
+
+        let response = transact(&mut io, &request).unwrap();
+        assert_eq!(io.written, REQUEST_DATA);
+        assert_eq!(response.as_slice(), RESPONSE_DATA);
+    }
+
+    #[test]
+    fn test_transact_error_report() {
+        let request = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
+        let mut busy = ResponsePacket::<[u8; 256]>::from_slice(&RESPONSE_DATA);
+        busy.set_report(crate::Report::Busy).calculate_checksum();
+        let mut io = Loopback::new(busy.as_slice());
+
+        let response = transact(&mut io, &request).unwrap();
+        assert!(matches!(response.report(), crate::Report::Busy));
+    }
+
+    #[test]
+    fn test_transact_retry() {
+        use crate::PacketError;
+        let request = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
+        // The report of RESPONSE_DATA is already Busy.
+        let busy = ResponsePacket::<[u8; 256]>::from_slice(&RESPONSE_DATA);
+        let mut normal = busy.clone();
+        normal
+            .set_report(crate::Report::Normal)
+            .calculate_checksum();
+        let replies = [busy.as_slice(), busy.as_slice(), normal.as_slice()].concat();
+
+        let mut delays = vec![];
+        let mut io = Loopback::new(&replies);
+        let response = transact_retry(&mut io, &request, 2, |n| delays.push(n)).unwrap();
+        assert_eq!(response.as_slice(), normal.as_slice());
+        assert_eq!(delays, [1, 2]);
+        assert_eq!(io.written, REQUEST_DATA.repeat(3));
+
+        let mut io = Loopback::new(&replies);
+        let err = transact_retry(&mut io, &request, 1, |_| {}).unwrap_err();
+        assert_eq!(
+            err.into_inner().unwrap().downcast_ref::<PacketError>(),
+            Some(&PacketError::TooManyRetries { retries: 1 })
+        );
+
+        let mut error = ResponsePacket::<[u8; 256]>::from_slice(&RESPONSE_DATA);
+        error
+            .set_report(crate::Report::InvalidData)
+            .calculate_checksum();
+        let mut io = Loopback::new(&[error.as_slice(), normal.as_slice()].concat());
+        let response = transact_retry(&mut io, &request, 2, |_| panic!("retried")).unwrap();
+        assert!(matches!(response.report(), crate::Report::InvalidData));
+    }
+
+    #[test]
+    fn test_transact_mismatch() {
+        use crate::PacketError;
+        let request = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
+        let mut other = ResponsePacket::<[u8; 256]>::from_slice(&RESPONSE_DATA);
+        other.next_sequence();
+        other.calculate_checksum();
+        let mut io = Loopback::new(other.as_slice());
+        let err = transact(&mut io, &request).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let mut corrupted = RESPONSE_DATA;
+        corrupted[9] = 0x00;
+        let mut io = Loopback::new(&corrupted);
+        let err = transact(&mut io, &request).unwrap_err();
+        assert_eq!(
+            err.into_inner().unwrap().downcast_ref::<PacketError>(),
+            Some(&PacketError::InvalidChecksum {
+                expected: 0x14,
+                got: 0x00
+            })
+        );
+    }
+
+    #[test]
+    fn test_response_packet_from_request() {
+        let request = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
+        let response = ResponsePacket::<[u8; 32]>::from_request(&request);
+
+        assert_eq!(response.sync(), crate::SYNC_BYTE);
+        assert_eq!(response.dest(), request.dest());
+        assert_eq!(response.sequence(), request.sequence());
+        assert_eq!(response.cmd(), request.cmd());
+        assert!(response.data_is_empty());
+        assert!(response.responds_to(&request));
+    }
+
+    #[test]
+    #[cfg(feature = "bitflags")]
+    fn test_response_packet_status_flags() {
+        let mut packet = ResponsePacket::<[u8; 256]>::from_slice(&RESPONSE_DATA);
+        packet.set_status_flags(StatusFlags::CHECKSUM_ERROR | StatusFlags::OVERFLOW);
+        assert_eq!(packet.status(), 0x0C);
+
+        let flags = packet.status_flags();
+        assert!(flags.contains(StatusFlags::CHECKSUM_ERROR));
+        assert!(flags.contains(StatusFlags::OVERFLOW));
+        assert!(!flags.contains(StatusFlags::UNSUPPORTED_COMMAND));
+
+        packet.set_status(0x82);
+        assert_eq!(packet.status_flags().bits(), 0x82);
+        assert!(packet
+            .status_flags()
+            .contains(StatusFlags::UNSUPPORTED_COMMAND));
+    }
+
+    #[test]
+    fn test_response_packet_responds_to() {
+        let mut request = RequestPacket::<[u8; 256]>::from_slice(&REQUEST_DATA);
+        let response = ResponsePacket::<[u8; 256]>::from_slice(&RESPONSE_DATA);
+        assert!(response.responds_to(&request));
+
+        request.next_sequence();
+        assert!(!response.responds_to(&request));
+        request.set_sequence(RESPONSE_DATA[3]).set_cmd(0x10);
+        assert!(!response.responds_to(&request));
+    }
+
     #[test]
     fn test_response_packet_read() {
         use crate::ReadPacket;
         let mut reader = std::io::Cursor::new(RESPONSE_DATA);
-        let mut packet = ResponsePacket::<256>::new();
+        let mut packet = ResponsePacket::<[u8; 256]>::new();
         reader.read_packet(&mut packet).unwrap();
 
         assert_eq!(reader.into_inner(), packet.as_slice())
@@ -228,7 +793,7 @@ mod tests {
     fn test_response_packet_write() {
         use crate::WritePacket;
         let mut writer = std::io::Cursor::new(vec![]);
-        let packet = ResponsePacket::<256>::from_slice(&RESPONSE_DATA);
+        let packet = ResponsePacket::<[u8; 256]>::from_slice(&RESPONSE_DATA);
         writer.write_packet_with_checksum(&packet).unwrap();
 
         assert_eq!(writer.into_inner(), packet.as_slice())
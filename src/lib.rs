@@ -1,6 +1,9 @@
 //! A packet structures for [JAMMA Video Standart] protocols.
 //! 
 //! This crate provides a wrapper around `[`[u8]`]` array with getter and setter methods for easily changing/writing/reading data.
+//!
+//! Packets are generic over their storage, so they can wrap a fixed array (the default, `[u8; 256]`),
+//! a heap allocated `Vec<u8>` or a borrowed `&mut [u8]`.
 //! 
 //! # Example
 //! ```
@@ -21,9 +24,33 @@
 
 mod packet;
 pub use packet::{
-    Packet, ReadByteExt, ReadPacket, Report, ReportField, WriteByteExt, WritePacket, MARK_BYTE, SYNC_BYTE,
+    escape_into, unescape_into, Checksum, ChecksumState, FrameKind, Packet, PacketError,
+    ReadByteExt, ReadPacket, ReadState, Report, ReportField, Unescape, UnescapeExt, WrappingSum,
+    WriteByteExt, WritePacket, BROADCAST_ADDRESS, MARK_BYTE, SYNC_BYTE,
 };
 
+mod any;
+pub use any::AnyPacket;
+
+mod reader;
+pub use reader::PacketReader;
+
+#[cfg(any(feature = "jvs", feature = "jvs_modified"))]
+mod dynamic;
+#[cfg(any(feature = "jvs", feature = "jvs_modified"))]
+pub use dynamic::{DynPacket, PacketKind};
+
+mod parser;
+pub use parser::{frame_length, scan_frames, PacketParser, ParserState};
+
+mod reassembler;
+pub use reassembler::Reassembler;
+
+#[cfg(feature = "tokio-util")]
+mod codec;
+#[cfg(feature = "tokio-util")]
+pub use codec::JvsCodec;
+
 #[cfg(feature = "jvs")]
 pub mod jvs;
 
@@ -33,11 +60,29 @@ pub mod jvs_modified;
 #[cfg(any(feature = "jvs", feature = "jvs_modified"))]
 macro_rules! impl_required_packet_blocks {
     ($t:tt) => {
-        impl<const N: usize> $t<N> {
+        impl<const N: usize> $t<[u8; N]> {
+            const MIN_SIZE_CHECK: () = assert!(
+                N > <Self as crate::Packet>::DATA_BEGIN_INDEX,
+                "N is too small to hold a minimal frame of the packet"
+            );
+
+            /// Creates a zeroed packet.
+            ///
+            /// Fails to compile if `N` is too small to hold a minimal frame of the packet.
             pub const fn new() -> Self {
+                let () = Self::MIN_SIZE_CHECK;
                 Self { inner: [0; N] }
             }
 
+            /// Creates a zeroed packet on the heap, without creating it on the stack first.
+            ///
+            /// Use it for large `N`, that would overflow the stack.
+            pub fn new_boxed() -> Box<Self> {
+                let () = Self::MIN_SIZE_CHECK;
+                // SAFETY: the packet consists of a `[u8; N]` array only, so all-zero bytes are a valid value.
+                unsafe { Box::<Self>::new_zeroed().assume_init() }
+            }
+
             pub fn from_reader(reader: &mut impl crate::ReadPacket) -> std::io::Result<Self> {
                 let mut packet = $t::new();
                 reader.read_packet(&mut packet)?;
@@ -45,6 +90,79 @@ macro_rules! impl_required_packet_blocks {
                 Ok(packet)
             }
 
+            /// Splits `data` into a sequence of packets addressed to `dest`.
+            ///
+            /// Each packet holds as much data as fits into both `N` bytes and the SIZE byte,
+            /// has the SYNC byte set and a calculated checksum, so it's valid on its own.
+            /// Returns an empty [`Vec`] if `data` is empty.
+            pub fn fragment(dest: u8, data: &[u8]) -> Vec<Self> {
+                use crate::Packet;
+                let max_by_size = u8::MAX as usize + Self::SIZE_INDEX - Self::DATA_BEGIN_INDEX;
+                let max_by_capacity = N - Self::DATA_BEGIN_INDEX - 1;
+
+                data.chunks(max_by_size.min(max_by_capacity))
+                    .map(|chunk| {
+                        let mut packet = Self::new();
+                        packet
+                            .set_sync()
+                            .set_dest(dest)
+                            .set_data(chunk)
+                            .calculate_checksum();
+                        packet
+                    })
+                    .collect()
+            }
+
+            /// Reads exactly one frame from the beginning of `buf` and advances it past the frame.
+            ///
+            /// Bytes before the SYNC byte and frames, that don't fit into `N` bytes, are skipped.
+            /// Returns [`None`] and leaves `buf` untouched if it doesn't contain a complete frame yet.
+            #[cfg(feature = "bytes")]
+            pub fn from_bytes(buf: &mut bytes::BytesMut) -> Option<Self> {
+                use bytes::Buf;
+                let mut packet = Self::new();
+                let len = crate::parser::decode(&mut packet, buf)?;
+                buf.advance(len);
+                Some(packet)
+            }
+
+            /// Creates a packet from a header and data, that come from separate buffers.
+            ///
+            /// `header` is copied as is, except for the SIZE byte, which is set from the length of `data`.
+            /// The checksum is calculated.
+            ///
+            /// # Errors
+            /// - [`crate::PacketError::InvalidHeaderLength`] if `header` is not [`crate::Packet::DATA_BEGIN_INDEX`] bytes long.
+            /// - [`crate::PacketError::BufferTooSmall`] if `data` doesn't fit into either `N` bytes or the SIZE byte.
+            pub fn assemble(header: &[u8], data: &[u8]) -> Result<Self, crate::PacketError> {
+                use crate::Packet;
+                if header.len() != Self::DATA_BEGIN_INDEX {
+                    return Err(crate::PacketError::InvalidHeaderLength {
+                        len: header.len(),
+                        expected: Self::DATA_BEGIN_INDEX,
+                    });
+                }
+
+                let mut packet = Self::new();
+                packet.inner[..header.len()].copy_from_slice(header);
+                packet
+                    .set_data_iter(data.iter().copied())?
+                    .calculate_checksum();
+                Ok(packet)
+            }
+
+            /// Creates a packet from a complete on-wire frame at the beginning of `wire`, unescaping it.
+            ///
+            /// Unlike [`Self::from_slice`], `wire` holds escaped bytes, e.g. captured from the bus.
+            /// Bytes after the end of the frame are ignored. See [`crate::Packet::decode_from`] for details and errors,
+            /// e.g. [`crate::PacketError::FrameTooShort`] for a SIZE byte, that leaves no place for the header and the SUM byte.
+            pub fn from_wire(wire: &[u8]) -> Result<Self, crate::PacketError> {
+                use crate::Packet;
+                let mut packet = Self::new();
+                packet.decode_from(wire)?;
+                Ok(packet)
+            }
+
             /// Initialize a struct from a slice.
             ///
             /// # Panics
@@ -57,23 +175,201 @@ macro_rules! impl_required_packet_blocks {
             }
         }
 
-        impl<const N: usize> AsRef<[u8]> for $t<N> {
+        impl<const N: usize> TryFrom<&[u8]> for $t<[u8; N]> {
+            type Error = crate::PacketError;
+
+            /// Initialize a struct from a slice, checking that it starts with [`crate::Packet::SYNC`],
+            /// contains at least a minimal frame, has the length described by the SIZE byte and fits into `N` bytes.
+            fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+                let min = <Self as crate::Packet>::DATA_BEGIN_INDEX + 1;
+                match slice.first() {
+                    Some(&b) if b == <Self as crate::Packet>::SYNC => {}
+                    Some(&b) => return Err(crate::PacketError::InvalidSync(b)),
+                    None => return Err(crate::PacketError::FrameTooShort { len: 0, min }),
+                }
+                if slice.len() < min {
+                    return Err(crate::PacketError::FrameTooShort {
+                        len: slice.len(),
+                        min,
+                    });
+                }
+                let expected = <Self as crate::Packet>::SIZE_INDEX
+                    + slice[<Self as crate::Packet>::SIZE_INDEX] as usize
+                    + 1;
+                if expected != slice.len() {
+                    return Err(crate::PacketError::TruncatedFrame {
+                        expected,
+                        got: slice.len(),
+                    });
+                }
+                if slice.len() > N {
+                    return Err(crate::PacketError::BufferTooSmall {
+                        len: slice.len(),
+                        capacity: N,
+                    });
+                }
+
+                Ok(Self::from_slice(slice))
+            }
+        }
+
+        impl $t<Vec<u8>> {
+            /// Creates a packet backed by a zeroed [`Vec`] of `capacity` bytes.
+            ///
+            /// # Panics
+            /// If `capacity` is too small to hold a minimal frame of the packet. Use [`Self::try_new`] to handle it.
+            pub fn with_capacity(capacity: usize) -> Self {
+                Self::try_new(capacity)
+                    .expect("the capacity is too small to hold a minimal frame of the packet")
+            }
+
+            /// Creates a packet backed by a zeroed [`Vec`] of `capacity` bytes.
+            ///
+            /// Returns [`None`] if `capacity` is too small to hold a minimal frame of the packet.
+            pub fn try_new(capacity: usize) -> Option<Self> {
+                (capacity > <Self as crate::Packet>::DATA_BEGIN_INDEX).then(|| Self {
+                    inner: vec![0; capacity],
+                })
+            }
+        }
+
+        impl<S: AsRef<[u8]> + AsMut<[u8]>> $t<S> {
+            /// Wraps an existing storage, e.g. a `Vec<u8>` or a borrowed `&mut [u8]`.
+            ///
+            /// The storage is used as is, so it should be zeroed or contain a valid packet.
+            ///
+            /// # Panics
+            /// If the storage is too small to hold a minimal frame of the packet. Use [`Self::try_from_storage`] to handle it.
+            pub fn from_storage(inner: S) -> Self {
+                Self::try_from_storage(inner)
+                    .expect("the storage is too small to hold a minimal frame of the packet")
+            }
+
+            /// Wraps an existing storage, like [`Self::from_storage`].
+            ///
+            /// Returns [`None`] if the storage is too small to hold a minimal frame of the packet.
+            pub fn try_from_storage(inner: S) -> Option<Self> {
+                (inner.as_ref().len() > <Self as crate::Packet>::DATA_BEGIN_INDEX)
+                    .then_some(Self { inner })
+            }
+
+            /// Consumes the packet and returns the underlying storage.
+            pub fn into_storage(self) -> S {
+                self.inner
+            }
+
+            /// Copies the frame of `other` into this packet.
+            ///
+            /// Useful for moving a frame between storages of different sizes, e.g. from a `[u8; 256]` parse buffer
+            /// into a compact `[u8; 32]` one. Bytes past the end of the copied frame are left untouched.
+            ///
+            /// Returns [`crate::PacketError::BufferTooSmall`] and leaves the packet unchanged if the frame doesn't fit.
+            pub fn copy_from<T: AsRef<[u8]> + AsMut<[u8]>>(
+                &mut self,
+                other: &$t<T>,
+            ) -> Result<(), crate::PacketError> {
+                use crate::Packet;
+                let frame = other.as_slice();
+                let capacity = self.inner.as_ref().len();
+                if frame.len() > capacity {
+                    return Err(crate::PacketError::BufferTooSmall {
+                        len: frame.len(),
+                        capacity,
+                    });
+                }
+
+                self.inner.as_mut()[..frame.len()].copy_from_slice(frame);
+                Ok(())
+            }
+        }
+
+        impl<S: AsRef<[u8]>> AsRef<[u8]> for $t<S> {
             fn as_ref(&self) -> &[u8] {
-                &self.inner
+                self.inner.as_ref()
             }
         }
 
-        impl<const N: usize> AsMut<[u8]> for $t<N> {
+        impl<S: AsMut<[u8]>> AsMut<[u8]> for $t<S> {
             fn as_mut(&mut self) -> &mut [u8] {
-                &mut self.inner
+                self.inner.as_mut()
             }
         }
 
-        impl<const N: usize> Default for $t<N> {
+        /// Compares the frame, i.e. [`crate::Packet::as_slice`], with the bytes. Bytes past the end of the frame are ignored.
+        impl<S: AsRef<[u8]> + AsMut<[u8]>> PartialEq<[u8]> for $t<S> {
+            fn eq(&self, other: &[u8]) -> bool {
+                crate::Packet::as_slice(self) == other
+            }
+        }
+
+        impl<S: AsRef<[u8]> + AsMut<[u8]>> PartialEq<&[u8]> for $t<S> {
+            fn eq(&self, other: &&[u8]) -> bool {
+                self == *other
+            }
+        }
+
+        impl<S: AsRef<[u8]> + AsMut<[u8]>, const M: usize> PartialEq<[u8; M]> for $t<S> {
+            fn eq(&self, other: &[u8; M]) -> bool {
+                self == &other[..]
+            }
+        }
+
+        impl<S: AsRef<[u8]> + AsMut<[u8]>> std::fmt::Debug for $t<S> {
+            /// Shows the header fields and the frame in hex. Bytes past the end of the frame are not shown.
+            ///
+            /// Only the SIZE byte and the frame are shown for a packet, whose SIZE byte doesn't describe a frame in the storage,
+            /// and the whole storage is shown if it's too small to hold even a minimal frame.
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                use crate::Packet;
+                let storage = self.inner.as_ref();
+                let mut s = f.debug_struct(stringify!($t));
+                if storage.len() <= Self::DATA_BEGIN_INDEX {
+                    return s
+                        .field("storage", &crate::packet::HexBytes(storage))
+                        .finish();
+                }
+                if !self.size_matches_data() {
+                    return s
+                        .field("size", &self.size())
+                        .field("frame", &crate::packet::HexBytes(self.as_slice()))
+                        .finish();
+                }
+                s.field("dest", &format_args!("{:#04x}", self.dest()))
+                    .field("size", &self.size())
+                    .field("data", &crate::packet::HexBytes(self.data()))
+                    .field("sum", &format_args!("{:#04x}", self.checksum()))
+                    .field("frame", &crate::packet::HexBytes(self.as_slice()))
+                    .finish()
+            }
+        }
+
+        impl<const N: usize> Default for $t<[u8; N]> {
             fn default() -> Self {
                 Self::new()
             }
         }
+
+        #[cfg(feature = "arbitrary")]
+        impl<'a, const N: usize> arbitrary::Arbitrary<'a> for $t<[u8; N]> {
+            /// Generates a well-formed packet with random header and data bytes,
+            /// a valid SIZE byte and a matching checksum.
+            ///
+            /// The amount of data is limited by both `N` and the SIZE byte.
+            fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+                use crate::Packet;
+                let max_by_size = u8::MAX as usize + Self::SIZE_INDEX - Self::DATA_BEGIN_INDEX;
+                let max_data = max_by_size.min(N - Self::DATA_BEGIN_INDEX - 1);
+                let data_len = u.int_in_range(0..=max_data)?;
+
+                let mut packet = Self::new();
+                u.fill_buffer(&mut packet.inner[1..Self::DATA_BEGIN_INDEX + data_len])?;
+                packet
+                    .set_sync()
+                    .set_size((Self::DATA_BEGIN_INDEX + data_len - Self::SIZE_INDEX) as u8)
+                    .calculate_checksum();
+                Ok(packet)
+            }
+        }
     };
 }
 
@@ -1,4 +1,7 @@
 use std::io::{self, Read, Write};
+
+use crate::PacketReader;
+
 /// SYNC byte indicates the beginning of the packet.
 ///
 /// Readers should skip bytes until the SYNC byte is found.
@@ -12,6 +15,134 @@ pub const SYNC_BYTE: u8 = 0xE0;
 /// [`SYNC_BYTE`] and [`MARK_BYTE`] bytes are escaped as `D0 DF` and `D0 CF` respectively. Altough any bytes can be escaped, only these 2 bytes requried escaping.
 pub const MARK_BYTE: u8 = 0xD0;
 
+/// Errors that can occur while constructing or validating a packet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PacketError {
+    /// The first byte of the frame is not a [`Packet::SYNC`] byte.
+    InvalidSync(u8),
+    /// The frame is shorter than the minimal frame for the packet type.
+    FrameTooShort { len: usize, min: usize },
+    /// The frame doesn't fit into the packet's storage.
+    BufferTooSmall { len: usize, capacity: usize },
+    /// The reader reached EOF in the middle of a frame.
+    ///
+    /// `expected` is the frame length known at the moment of EOF, `got` is the number of bytes read.
+    TruncatedFrame { expected: usize, got: usize },
+    /// [`MARK_BYTE`] is followed by a byte, that doesn't need escaping.
+    InvalidEscape(u8),
+    /// The fragment at `index` has an invalid checksum.
+    FragmentChecksum { index: usize },
+    /// The SUM byte doesn't match the checksum calculated from the frame.
+    InvalidChecksum { expected: u8, got: u8 },
+    /// The frame takes more than `max` bytes on the wire.
+    FrameTooLong { max: usize },
+    /// The packet data is shorter than a command response requires.
+    DataTooShort { len: usize, expected: usize },
+    /// A string in the packet data is not terminated by a null byte.
+    MissingTerminator,
+    /// A string in the packet data contains a non-ASCII byte at `index`.
+    NonAscii { index: usize, byte: u8 },
+    /// The device was still busy after `retries` retries.
+    TooManyRetries { retries: usize },
+    /// None of the `frames` read frames matched.
+    NoMatchingFrame { frames: usize },
+    /// The header is `len` bytes long instead of [`Packet::DATA_BEGIN_INDEX`] bytes.
+    InvalidHeaderLength { len: usize, expected: usize },
+    /// An argument of a parsing method doesn't describe a valid layout of the data.
+    InvalidArgument(&'static str),
+}
+
+impl std::fmt::Display for PacketError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PacketError::InvalidSync(b) => write!(f, "Unexpected SYNC byte: {:#04x}", b),
+            PacketError::FrameTooShort { len, min } => {
+                write!(
+                    f,
+                    "The frame is too short: {} bytes, expected at least {}",
+                    len, min
+                )
+            }
+            PacketError::BufferTooSmall { len, capacity } => {
+                write!(
+                    f,
+                    "The frame of {} bytes doesn't fit into a buffer of {} bytes",
+                    len, capacity
+                )
+            }
+            PacketError::TruncatedFrame { expected, got } => {
+                write!(f, "The frame is truncated: got {} of {} bytes", got, expected)
+            }
+            PacketError::InvalidEscape(b) => {
+                write!(f, "Invalid escaped byte after MARK byte (0xD0): {:#04x}", b)
+            }
+            PacketError::FragmentChecksum { index } => {
+                write!(f, "The fragment {} has an invalid checksum", index)
+            }
+            PacketError::InvalidChecksum { expected, got } => {
+                write!(
+                    f,
+                    "Invalid checksum: expected {:#04x}, got {:#04x}",
+                    expected, got
+                )
+            }
+            PacketError::FrameTooLong { max } => {
+                write!(f, "The frame takes more than {} bytes on the wire", max)
+            }
+            PacketError::DataTooShort { len, expected } => {
+                write!(
+                    f,
+                    "The packet data is too short: {} bytes, expected {}",
+                    len, expected
+                )
+            }
+            PacketError::MissingTerminator => {
+                write!(
+                    f,
+                    "The string in the packet data is not terminated by a null byte"
+                )
+            }
+            PacketError::NonAscii { index, byte } => {
+                write!(
+                    f,
+                    "Non-ASCII byte {:#04x} at index {} of the string",
+                    byte, index
+                )
+            }
+            PacketError::TooManyRetries { retries } => {
+                write!(f, "The device is still busy after {} retries", retries)
+            }
+            PacketError::NoMatchingFrame { frames } => {
+                write!(f, "None of {} read frames matched", frames)
+            }
+            PacketError::InvalidHeaderLength { len, expected } => {
+                write!(
+                    f,
+                    "Invalid header length: {} bytes, expected {}",
+                    len, expected
+                )
+            }
+            PacketError::InvalidArgument(msg) => write!(f, "Invalid argument: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for PacketError {}
+
+impl From<PacketError> for io::Error {
+    fn from(value: PacketError) -> Self {
+        let kind = match value {
+            PacketError::TruncatedFrame { .. } => io::ErrorKind::UnexpectedEof,
+            PacketError::TooManyRetries { .. } => io::ErrorKind::Other,
+            _ => io::ErrorKind::InvalidData,
+        };
+        io::Error::new(kind, value)
+    }
+}
+
+/// Broadcast address. Packets sent to it are addressed to every node on the bus.
+pub const BROADCAST_ADDRESS: u8 = 0xFF;
+
 /// JVS response report codes.
 /// 
 /// When slave sending response to master, it will always contain a report code, which is placed before first DATA byte.
@@ -19,7 +150,10 @@ pub const MARK_BYTE: u8 = 0xD0;
 /// The Report byte indicates whether a request was completed succesfully.
 /// 
 /// Check variants documentation if you need to know what which code does.
+///
+/// The enum is `#[non_exhaustive]`, since new report codes may be added, so matches on it must include a wildcard arm.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum Report {
     /// Request was processed successfully.
     Normal = 1,
@@ -45,14 +179,170 @@ impl From<u8> for Report {
     }
 }
 
+impl From<Report> for u8 {
+    /// Returns the REPORT byte of the report code.
+    ///
+    /// [`Report::Unknown`] doesn't keep the original byte, so it's converted to `0x00`, that isn't a valid report code
+    /// and converts back to [`Report::Unknown`].
+    fn from(report: Report) -> Self {
+        match report {
+            Report::Normal => 1,
+            Report::IncorrectDataSize => 2,
+            Report::InvalidData => 3,
+            Report::Busy => 4,
+            Report::Unknown => 0,
+        }
+    }
+}
+
+impl Report {
+    /// Converts the report into a [`Result`], that is `Ok(())` for [`Report::Normal`] and `Err(self)` otherwise.
+    ///
+    /// # Example
+    /// ```
+    /// use jvs_packets::{jvs::ResponsePacket, Packet, ReportField};
+    ///
+    /// fn handle(packet: &ResponsePacket) -> Result<u8, Box<dyn std::error::Error>> {
+    ///     packet.report().into_result()?;
+    ///     Ok(packet.data()[0])
+    /// }
+    ///
+    /// let ok = ResponsePacket::from_slice(&[0xE0, 0x00, 0x03, 0x01, 0x10, 0x14]);
+    /// assert_eq!(handle(&ok).unwrap(), 0x10);
+    ///
+    /// let busy = ResponsePacket::from_slice(&[0xE0, 0x00, 0x03, 0x04, 0x10, 0x17]);
+    /// assert_eq!(handle(&busy).unwrap_err().to_string(), "device I/O busy");
+    /// ```
+    pub fn into_result(self) -> Result<(), Report> {
+        match self {
+            Report::Normal => Ok(()),
+            report => Err(report),
+        }
+    }
+}
+
+impl std::fmt::Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            Report::Normal => "normal",
+            Report::IncorrectDataSize => "incorrect number of parameters",
+            Report::InvalidData => "invalid data",
+            Report::Busy => "device I/O busy",
+            Report::Unknown => "unknown report code",
+        };
+        f.write_str(text)
+    }
+}
+
+impl std::error::Error for Report {}
+
+/// A checksum algorithm used by the protocol.
+///
+/// Standard JVS uses [`WrappingSum`], but some JVS-derived devices use a different algorithm.
+pub trait Checksum {
+    /// Calculates a checksum of the given bytes.
+    fn checksum(bytes: &[u8]) -> u8;
+}
+
+/// The standard JVS checksum, an 8-bit wrapping sum of all bytes.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WrappingSum;
+
+impl Checksum for WrappingSum {
+    fn checksum(bytes: &[u8]) -> u8 {
+        bytes.iter().fold(0, |acc: u8, &x| acc.wrapping_add(x))
+    }
+}
+
+/// An incremental [`WrappingSum`], for calculating the checksum of a frame, that passes through byte by byte.
+///
+/// Feed it the unescaped bytes between the SYNC and the SUM bytes, like [`Packet::calculate_checksum`] does.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChecksumState {
+    sum: u8,
+}
+
+impl ChecksumState {
+    pub const fn new() -> Self {
+        Self { sum: 0 }
+    }
+
+    /// Adds a byte to the checksum.
+    pub fn update(&mut self, byte: u8) {
+        self.sum = self.sum.wrapping_add(byte);
+    }
+
+    /// Returns the checksum of all bytes fed so far.
+    pub fn finish(self) -> u8 {
+        self.sum
+    }
+}
+
+/// The direction of a frame on the bus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FrameKind {
+    /// A frame sent by the master to a slave.
+    Request,
+    /// A frame sent by a slave to the master. Responses carry a REPORT byte.
+    Response,
+}
+
 /// A trait for all packets structures
 pub trait Packet: AsRef<[u8]> + AsMut<[u8]> {
     const SIZE_INDEX: usize;
     const DATA_BEGIN_INDEX: usize;
     const DESTINATION_INDEX: usize;
+    /// Whether the packet type is a request or a response.
+    const KIND: FrameKind;
+    /// The byte, that indicates the beginning of the packet.
+    ///
+    /// Defaults to [`SYNC_BYTE`]. Override it for JVS-derived protocols with a different start byte.
+    const SYNC: u8 = SYNC_BYTE;
+    /// The byte, that precedes an escaped byte on the wire.
+    ///
+    /// Defaults to [`MARK_BYTE`].
+    const MARK: u8 = MARK_BYTE;
 
+    /// Returns `true` if `b` must be escaped on the wire.
+    ///
+    /// By default [`Packet::SYNC`] and [`Packet::MARK`] are escaped.
+    /// Override it for JVS-derived protocols, that escape additional bytes.
+    fn is_escaped_byte(b: u8) -> bool
+    where
+        Self: Sized,
+    {
+        b == Self::SYNC || b == Self::MARK
+    }
+
+    /// Returns [`Packet::SIZE_INDEX`].
+    ///
+    /// `Packet` has associated constants, so it can't be used as a trait object.
+    /// This and the other index methods allow exposing the framing through a dyn-compatible trait.
+    fn size_index(&self) -> usize {
+        Self::SIZE_INDEX
+    }
+
+    /// Returns [`Packet::DATA_BEGIN_INDEX`].
+    fn data_begin_index(&self) -> usize {
+        Self::DATA_BEGIN_INDEX
+    }
+
+    /// Returns [`Packet::DESTINATION_INDEX`].
+    fn destination_index(&self) -> usize {
+        Self::DESTINATION_INDEX
+    }
+
+    /// Returns [`Packet::KIND`].
+    fn frame_kind(&self) -> FrameKind {
+        Self::KIND
+    }
+
+    /// Returns the length of the frame according to the SIZE byte.
+    ///
+    /// The length is clamped to the length of the buffer, so a garbage SIZE byte results in a truncated frame
+    /// instead of an out-of-bounds access in the slice accessors.
     fn len_of_packet(&self) -> usize {
-        Self::SIZE_INDEX + self.as_ref()[Self::SIZE_INDEX] as usize + 1
+        frame_len(self).min(self.as_ref().len())
     }
 
     /// Returns a slice of the packet until SUM byte.
@@ -60,19 +350,47 @@ pub trait Packet: AsRef<[u8]> + AsMut<[u8]> {
         &self.as_ref()[..self.len_of_packet()]
     }
 
+    /// Returns the header of the packet: the bytes from SYNC up to [`Packet::DATA_BEGIN_INDEX`].
+    ///
+    /// For responses the header includes the REPORT byte.
+    fn header(&self) -> &[u8] {
+        &self.as_ref()[..Self::DATA_BEGIN_INDEX]
+    }
+
     /// Returns a mutable slice of the packet until SUM byte.
     fn as_mut_slice(&mut self) -> &mut [u8] {
         let len = self.len_of_packet();
         &mut self.as_mut()[..len]
     }
 
+    /// Zeroes the whole underlying buffer, including bytes past the end of the current frame.
+    fn clear(&mut self) -> &mut Self {
+        self.as_mut().fill(0);
+        self
+    }
+
+    /// Prepares the packet for building a new frame without zeroing the whole buffer like [`Packet::clear`] does.
+    ///
+    /// Sets the SYNC byte, zeroes the rest of the header and sets the SIZE byte to hold no data,
+    /// so the packet is a valid empty frame with a calculated checksum. Bytes past the header are left untouched.
+    ///
+    /// # Panics
+    /// If the buffer is too small to hold a minimal frame, i.e. is not longer than [`Packet::DATA_BEGIN_INDEX`].
+    fn reset(&mut self) -> &mut Self {
+        self.as_mut()[1..=Self::DATA_BEGIN_INDEX].fill(0);
+        self.set_sync()
+            .set_size((Self::DATA_BEGIN_INDEX - Self::SIZE_INDEX) as u8)
+            .calculate_checksum()
+    }
+
     /// Returns a first byte in the slice.
     fn sync(&self) -> u8 {
         self.as_ref()[0]
     }
 
+    /// Sets a first byte to [`Packet::SYNC`].
     fn set_sync(&mut self) -> &mut Self {
-        self.as_mut()[0] = SYNC_BYTE;
+        self.as_mut()[0] = Self::SYNC;
         self
     }
 
@@ -94,15 +412,66 @@ pub trait Packet: AsRef<[u8]> + AsMut<[u8]> {
         self.as_ref()[Self::DESTINATION_INDEX]
     }
 
-    /// Sets a destination byte at [`Packet::DESTINATION_INDEX`] and calculates a new checksum.
+    /// Sets a destination byte at [`Packet::DESTINATION_INDEX`].
+    ///
+    /// Like other setters, it doesn't recalculate the checksum. Use [`Packet::set_dest_and_checksum`] for that.
     fn set_dest(&mut self, dest: u8) -> &mut Self {
         self.as_mut()[Self::DESTINATION_INDEX] = dest;
         self
     }
 
+    /// Sets a destination byte at [`Packet::DESTINATION_INDEX`] and calculates a new checksum.
+    ///
+    /// Useful for readdressing a complete frame, e.g. when forwarding it to another node.
+    fn set_dest_and_checksum(&mut self, dest: u8) -> &mut Self {
+        self.set_dest(dest).calculate_checksum()
+    }
+
+    /// Returns `true` if the packet is addressed to `addr`, either directly or via [`BROADCAST_ADDRESS`].
+    ///
+    /// Useful for routing packets from several nodes to their handlers.
+    fn matches_address(&self, addr: u8) -> bool {
+        let dest = self.dest();
+        dest == addr || dest == BROADCAST_ADDRESS
+    }
+
     /// Returns a slice of the packet data.
+    ///
+    /// The slice is empty if the SIZE byte is too small to hold any data.
     fn data(&self) -> &[u8] {
-        &self.as_ref()[Self::DATA_BEGIN_INDEX..self.len_of_packet() - 1]
+        let end = self.len_of_packet() - 1;
+        &self.as_ref()[Self::DATA_BEGIN_INDEX.min(end)..end]
+    }
+
+    /// Returns a mutable slice of the packet data.
+    ///
+    /// The slice is bounded by the current SIZE byte, so the length of the data can't be changed through it.
+    /// The checksum is not updated, call [`Packet::calculate_checksum`] after editing.
+    fn data_mut(&mut self) -> &mut [u8] {
+        let end = self.len_of_packet() - 1;
+        &mut self.as_mut()[Self::DATA_BEGIN_INDEX.min(end)..end]
+    }
+
+    /// Returns an iterator over the DATA bytes of the packet.
+    fn data_iter(&self) -> std::iter::Copied<std::slice::Iter<'_, u8>> {
+        self.data().iter().copied()
+    }
+
+    /// Returns the number of DATA bytes in the packet.
+    ///
+    /// Header bytes (including the REPORT byte in responses) and the SUM byte are not counted.
+    fn data_len(&self) -> usize {
+        self.len_of_packet().saturating_sub(Self::DATA_BEGIN_INDEX + 1)
+    }
+
+    /// Returns `true` if the packet contains no DATA bytes.
+    fn data_is_empty(&self) -> bool {
+        self.data_len() == 0
+    }
+
+    /// Returns how many more DATA bytes fit into the packet, limited by both the buffer and the SIZE byte.
+    fn remaining_capacity(&self) -> usize {
+        max_data_len(self).saturating_sub(self.data_len())
     }
 
     /// Sets the packet data.
@@ -115,18 +484,426 @@ pub trait Packet: AsRef<[u8]> + AsMut<[u8]> {
         self
     }
 
+    /// Sets the packet data from an iterator, without collecting it into a slice first.
+    ///
+    /// Like [`Packet::set_data`], this method also sets the size byte.
+    ///
+    /// # Errors
+    /// Returns [`PacketError::BufferTooSmall`] as soon as the data doesn't fit into either the buffer or the SIZE byte.
+    /// The DATA bytes written so far are kept in that case, but the SIZE byte is left unchanged.
+    fn set_data_iter(
+        &mut self,
+        data: impl IntoIterator<Item = u8>,
+    ) -> Result<&mut Self, PacketError> {
+        let max = max_data_len(self);
+
+        let mut len = 0;
+        for b in data {
+            if len == max {
+                return Err(PacketError::BufferTooSmall {
+                    len: Self::DATA_BEGIN_INDEX + len + 2,
+                    capacity: Self::DATA_BEGIN_INDEX + max + 1,
+                });
+            }
+            self.as_mut()[Self::DATA_BEGIN_INDEX + len] = b;
+            len += 1;
+        }
+
+        Ok(self.set_size((Self::DATA_BEGIN_INDEX + len - Self::SIZE_INDEX) as u8))
+    }
+
+    /// Sets the packet data to `count` copies of `byte`, e.g. for padding or test vectors.
+    ///
+    /// Like [`Packet::set_data`], this method also sets the size byte.
+    ///
+    /// # Errors
+    /// Returns [`PacketError::BufferTooSmall`] and leaves the packet unchanged if `count` bytes don't fit into either the buffer or the SIZE byte.
+    fn fill_data(&mut self, byte: u8, count: usize) -> Result<&mut Self, PacketError> {
+        let max = max_data_len(self);
+        if count > max {
+            return Err(PacketError::BufferTooSmall {
+                len: Self::DATA_BEGIN_INDEX + count + 1,
+                capacity: Self::DATA_BEGIN_INDEX + max + 1,
+            });
+        }
+
+        self.as_mut()[Self::DATA_BEGIN_INDEX..Self::DATA_BEGIN_INDEX + count].fill(byte);
+        Ok(self.set_size((Self::DATA_BEGIN_INDEX + count - Self::SIZE_INDEX) as u8))
+    }
+
+    /// Shortens the packet data to `new_len` bytes by setting the size byte. The first `new_len` bytes are kept.
+    ///
+    /// If `new_len` is not less than [`Packet::data_len`], the packet is left unchanged.
+    /// The checksum is not recalculated, call [`Packet::calculate_checksum`] afterwards.
+    fn truncate_data(&mut self, new_len: usize) -> &mut Self {
+        if new_len >= self.data_len() {
+            return self;
+        }
+        self.set_size((Self::DATA_BEGIN_INDEX + new_len - Self::SIZE_INDEX) as u8)
+    }
+
+    /// Appends `extra` after the current packet data and updates the size byte, e.g. for batching several commands into one request.
+    ///
+    /// The checksum is not recalculated, call [`Packet::calculate_checksum`] afterwards.
+    ///
+    /// # Errors
+    /// Returns [`PacketError::BufferTooSmall`] and leaves the packet unchanged if the data doesn't fit into either the buffer or the SIZE byte.
+    fn extend_data(&mut self, extra: &[u8]) -> Result<&mut Self, PacketError> {
+        let len = self.data_len();
+        let new_len = len + extra.len();
+        let max = max_data_len(self);
+        if new_len > max {
+            return Err(PacketError::BufferTooSmall {
+                len: Self::DATA_BEGIN_INDEX + new_len + 1,
+                capacity: Self::DATA_BEGIN_INDEX + max + 1,
+            });
+        }
+
+        let begin = Self::DATA_BEGIN_INDEX + len;
+        self.as_mut()[begin..begin + extra.len()].copy_from_slice(extra);
+        Ok(self.set_size((Self::DATA_BEGIN_INDEX + new_len - Self::SIZE_INDEX) as u8))
+    }
+
+    /// Returns two DATA bytes at `offset` as a big-endian `u16`, which is the byte order of the JVS spec.
+    ///
+    /// # Panics
+    /// If `offset + 2` exceeds [`Packet::data_len`].
+    fn data_u16_be(&self, offset: usize) -> u16 {
+        u16::from_be_bytes(data_bytes(self, offset))
+    }
+
+    /// Returns two DATA bytes at `offset` as a little-endian `u16`.
+    ///
+    /// # Panics
+    /// If `offset + 2` exceeds [`Packet::data_len`].
+    fn data_u16_le(&self, offset: usize) -> u16 {
+        u16::from_le_bytes(data_bytes(self, offset))
+    }
+
+    /// Writes `value` as two big-endian DATA bytes at `offset`, which is the byte order of the JVS spec.
+    ///
+    /// If the bytes extend past the current data, the SIZE byte is updated to include them. The checksum is not updated.
+    ///
+    /// # Panics
+    /// If the bytes don't fit into the packet.
+    fn set_data_u16_be(&mut self, offset: usize, value: u16) -> &mut Self {
+        set_data_bytes(self, offset, &value.to_be_bytes())
+    }
+
+    /// Writes `value` as two little-endian DATA bytes at `offset`.
+    ///
+    /// If the bytes extend past the current data, the SIZE byte is updated to include them. The checksum is not updated.
+    ///
+    /// # Panics
+    /// If the bytes don't fit into the packet.
+    fn set_data_u16_le(&mut self, offset: usize, value: u16) -> &mut Self {
+        set_data_bytes(self, offset, &value.to_le_bytes())
+    }
+
+    /// Returns four DATA bytes at `offset` as a big-endian `u32`, e.g. a card ID.
+    ///
+    /// # Panics
+    /// If `offset + 4` exceeds [`Packet::data_len`].
+    fn data_u32_be(&self, offset: usize) -> u32 {
+        u32::from_be_bytes(data_bytes(self, offset))
+    }
+
+    /// Returns four DATA bytes at `offset` as a little-endian `u32`.
+    ///
+    /// # Panics
+    /// If `offset + 4` exceeds [`Packet::data_len`].
+    fn data_u32_le(&self, offset: usize) -> u32 {
+        u32::from_le_bytes(data_bytes(self, offset))
+    }
+
+    /// Writes `value` as four big-endian DATA bytes at `offset`.
+    ///
+    /// If the bytes extend past the current data, the SIZE byte is updated to include them. The checksum is not updated.
+    ///
+    /// # Panics
+    /// If the bytes don't fit into the packet.
+    fn set_data_u32_be(&mut self, offset: usize, value: u32) -> &mut Self {
+        set_data_bytes(self, offset, &value.to_be_bytes())
+    }
+
+    /// Writes `value` as four little-endian DATA bytes at `offset`.
+    ///
+    /// If the bytes extend past the current data, the SIZE byte is updated to include them. The checksum is not updated.
+    ///
+    /// # Panics
+    /// If the bytes don't fit into the packet.
+    fn set_data_u32_le(&mut self, offset: usize, value: u32) -> &mut Self {
+        set_data_bytes(self, offset, &value.to_le_bytes())
+    }
+
+    /// Returns the range of indices of the bytes, that the checksum is calculated over.
+    ///
+    /// The range starts after the SYNC byte and ends before the SUM byte, i.e. it's `1..len - 1`,
+    /// where `len` is [`Packet::len_of_packet`]. It's empty for packets, that can't have a checksum.
+    /// See [`Packet::calculate_checksum_with`].
+    fn checksum_range(&self) -> std::ops::Range<usize> {
+        if !has_checksum_place(self) {
+            return 1..1;
+        }
+        1..self.len_of_packet() - 1
+    }
+
     /// Calculates checksum.
     ///
-    /// The checksum is calculated by summing all bytes in the packet except the [`SYNC_BYTE`].
+    /// The checksum is calculated by summing all bytes in the packet except the [`SYNC_BYTE`]. See [`WrappingSum`].
     fn calculate_checksum(&mut self) -> &mut Self {
-        self.set_checksum(
-            self.as_slice()
-                .iter()
-                .skip(1)
-                .take(self.len_of_packet() - 2)
-                .fold(0, |acc: u8, &x| acc.wrapping_add(x)),
-        );
-        self
+        self.calculate_checksum_with(WrappingSum::checksum)
+    }
+
+    /// Calculates checksum with a custom algorithm.
+    ///
+    /// `f` receives all bytes in the packet except the [`SYNC_BYTE`] and the SUM byte.
+    /// Any [`Checksum::checksum`] can be passed here, e.g. `packet.calculate_checksum_with(WrappingSum::checksum)`.
+    ///
+    /// Does nothing if the SIZE byte is too small for the SUM byte to follow the header
+    /// or too large for the packet to fit in the buffer, since there is no place to write the checksum to.
+    fn calculate_checksum_with(&mut self, f: impl Fn(&[u8]) -> u8) -> &mut Self {
+        if !has_checksum_place(self) {
+            return self;
+        }
+        let checksum = f(&self.as_ref()[self.checksum_range()]);
+        self.set_checksum(checksum)
+    }
+
+    /// Recalculates the checksum after the packet was edited in place, e.g. via [`Packet::data_mut`].
+    ///
+    /// The SIZE byte is left as is, since in-place edits don't change the length of the packet.
+    /// Use [`Packet::finalize`] when the data is replaced as a whole.
+    fn recalculate(&mut self) -> &mut Self {
+        self.calculate_checksum()
+    }
+
+    /// Sets the packet data, SIZE byte and checksum in one call.
+    ///
+    /// The result is a complete frame, as long as the SYNC and destination bytes are set.
+    /// Use [`Packet::recalculate`] instead after editing the data in place.
+    fn finalize(&mut self, data: &[u8]) -> &mut Self {
+        self.set_data(data).calculate_checksum()
+    }
+
+    /// Returns `true` if the stored checksum matches the one calculated with [`WrappingSum`].
+    ///
+    /// Always returns `false` for packets, that can't have a checksum. See [`Packet::calculate_checksum_with`].
+    #[must_use]
+    fn verify_checksum(&self) -> bool {
+        if !has_checksum_place(self) {
+            return false;
+        }
+        WrappingSum::checksum(&self.as_ref()[self.checksum_range()]) == self.checksum()
+    }
+
+    /// Returns `true` if the escaped on-wire frame at the beginning of `wire` has a valid checksum, without decoding it into a packet.
+    ///
+    /// The checksum is calculated over the unescaped bytes, not over the bytes on the wire: MARK bytes are not counted
+    /// and each escaped byte is counted with its original value, e.g. `D0 DF` adds `0xE0`. The SUM byte may be escaped too.
+    ///
+    /// Returns `false` if `wire` doesn't start with [`Packet::SYNC`], ends or is interrupted by a SYNC byte before the frame is complete,
+    /// or the SIZE byte leaves no place for the SUM byte.
+    fn checksum_is_valid_for_wire(wire: &[u8]) -> bool
+    where
+        Self: Sized,
+    {
+        if wire.first() != Some(&Self::SYNC) {
+            return false;
+        }
+
+        let mut state = ChecksumState::new();
+        let mut got = 1;
+        let mut expected = Self::SIZE_INDEX + 1;
+        let mut bytes = wire[1..].iter().copied();
+        while let Some(b) = bytes.next() {
+            let b = match b {
+                b if b == Self::SYNC => return false,
+                b if b == Self::MARK => match bytes.next() {
+                    Some(b) => b.wrapping_add(1),
+                    None => return false,
+                },
+                b => b,
+            };
+
+            got += 1;
+            if got == Self::SIZE_INDEX + 1 {
+                expected = b as usize + Self::SIZE_INDEX + 1;
+                if expected <= Self::DATA_BEGIN_INDEX {
+                    return false;
+                }
+            } else if got == expected {
+                return state.finish() == b;
+            }
+            state.update(b);
+        }
+
+        false
+    }
+
+    /// Returns `true` if the SIZE byte describes a frame, that covers the header, has a place for the SUM byte and fits into the buffer.
+    ///
+    /// A cheap check for a SIZE byte, that was set manually with [`Packet::set_size`]. Unlike [`Packet::validate`], it ignores
+    /// the SYNC byte and the checksum.
+    fn size_matches_data(&self) -> bool {
+        checked_frame_len(self).is_ok()
+    }
+
+    /// Checks that the packet is a well-formed frame and returns the first violated invariant.
+    ///
+    /// # Errors
+    /// - [`PacketError::InvalidSync`] if the first byte is not [`Packet::SYNC`].
+    /// - [`PacketError::FrameTooShort`] if the SIZE byte is too small for the SUM byte to follow the header,
+    ///   i.e. the frame doesn't contain every header field, e.g. the STATUS, CMD and REPORT bytes of a modified response.
+    /// - [`PacketError::BufferTooSmall`] if the SIZE byte points past the end of the buffer.
+    /// - [`PacketError::InvalidChecksum`] if the SUM byte doesn't match the one calculated with [`WrappingSum`].
+    fn validate(&self) -> Result<(), PacketError> {
+        if self.sync() != Self::SYNC {
+            return Err(PacketError::InvalidSync(self.sync()));
+        }
+        checked_frame_len(self)?;
+        let expected = WrappingSum::checksum(&self.as_ref()[self.checksum_range()]);
+        if expected != self.checksum() {
+            return Err(PacketError::InvalidChecksum {
+                expected,
+                got: self.checksum(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of bytes [`WritePacket::write_packet_with_checksum`] would write for this packet.
+    ///
+    /// Differs from [`Packet::len_of_packet`] since every byte, that needs escaping (including the checksum), takes two bytes on the wire.
+    /// The checksum is calculated from the packet bytes, so a stale SUM byte doesn't affect the result.
+    fn wire_len(&self) -> usize
+    where
+        Self: Sized,
+    {
+        let body = &self.as_slice()[1..self.len_of_packet() - 1];
+        let escaped_len = |b: u8| if Self::is_escaped_byte(b) { 2 } else { 1 };
+
+        1 + body.iter().map(|&b| escaped_len(b)).sum::<usize>()
+            + escaped_len(WrappingSum::checksum(body))
+    }
+
+    /// Returns `true` if any byte of the frame after the SYNC byte is escaped on the wire.
+    ///
+    /// Such frames are longer on the wire than [`Packet::len_of_packet`] and may trip up a naive parser.
+    fn needs_escaping(&self) -> bool
+    where
+        Self: Sized,
+    {
+        self.as_slice()[1..]
+            .iter()
+            .any(|&b| Self::is_escaped_byte(b))
+    }
+
+    /// Writes the escaped on-wire bytes of the packet into `out` and returns the number of written bytes.
+    ///
+    /// The output is exactly what [`WritePacket::write_packet_with_checksum`] would write, but without allocating or using [`std::io::Write`].
+    ///
+    /// # Errors
+    /// - [`PacketError::FrameTooShort`] or [`PacketError::BufferTooSmall`] if the SIZE byte doesn't describe a frame, that fits into the packet.
+    /// - [`PacketError::BufferTooSmall`] with `out.len()` as capacity if `out` is shorter than [`Packet::wire_len`]. Nothing is written in that case.
+    fn encode_into(&self, out: &mut [u8]) -> Result<usize, PacketError>
+    where
+        Self: Sized,
+    {
+        let len = checked_frame_len(self)?;
+        let wire_len = self.wire_len();
+        if wire_len > out.len() {
+            return Err(PacketError::BufferTooSmall {
+                len: wire_len,
+                capacity: out.len(),
+            });
+        }
+
+        let body = &self.as_slice()[1..len - 1];
+        out[0] = Self::SYNC;
+        let mut pos = 1;
+        for b in body.iter().copied().chain([WrappingSum::checksum(body)]) {
+            if Self::is_escaped_byte(b) {
+                out[pos] = Self::MARK;
+                out[pos + 1] = b.wrapping_sub(1);
+                pos += 2;
+            } else {
+                out[pos] = b;
+                pos += 1;
+            }
+        }
+
+        Ok(pos)
+    }
+
+    /// Unescapes a complete on-wire frame from the beginning of `wire` into the packet and returns the number of consumed wire bytes.
+    ///
+    /// Decoding stops at the end of the frame, determined by the SIZE byte, so bytes of the next frame are left untouched.
+    /// This is the counterpart of [`Packet::encode_into`]. The checksum is copied as is, use [`Packet::validate`] to check it.
+    ///
+    /// # Errors
+    /// - [`PacketError::InvalidSync`] if `wire` doesn't start with [`Packet::SYNC`].
+    /// - [`PacketError::TruncatedFrame`] if `wire` ends, or a new SYNC byte appears, before the frame is complete.
+    /// - [`PacketError::FrameTooShort`] if the SIZE byte is too small for a minimal frame.
+    /// - [`PacketError::BufferTooSmall`] if the frame doesn't fit into the packet.
+    fn decode_from(&mut self, wire: &[u8]) -> Result<usize, PacketError>
+    where
+        Self: Sized,
+    {
+        match wire.first() {
+            Some(&b) if b == Self::SYNC => {}
+            Some(&b) => return Err(PacketError::InvalidSync(b)),
+            None => {
+                return Err(PacketError::TruncatedFrame {
+                    expected: Self::SIZE_INDEX + 1,
+                    got: 0,
+                })
+            }
+        }
+
+        let mut state = ReadState::new();
+        for (i, &b) in wire.iter().enumerate() {
+            if i > 0 && b == Self::SYNC {
+                break;
+            }
+            if state.push(self, b)? {
+                return Ok(i + 1);
+            }
+        }
+
+        let got = state.pos();
+        let expected = if got > Self::SIZE_INDEX {
+            frame_len(self)
+        } else {
+            Self::SIZE_INDEX + 1
+        };
+        Err(PacketError::TruncatedFrame { expected, got })
+    }
+
+    /// Returns a [`PacketReader`], that implements [`std::io::Read`] and yields the escaped on-wire bytes of the packet.
+    ///
+    /// Useful for passing a packet to code, that expects a [`std::io::Read`], e.g. [`std::io::copy`].
+    fn reader(&self) -> PacketReader<'_, Self>
+    where
+        Self: Sized,
+    {
+        PacketReader::new(self)
+    }
+
+    /// Returns the escaped on-wire bytes of the packet, exactly what [`WritePacket::write_packet_with_checksum`] would write.
+    ///
+    /// # Panics
+    /// If the SIZE byte doesn't describe a frame, that can be written. See [`WritePacket::write_packet`].
+    fn as_bytes_escaped(&self) -> Vec<u8>
+    where
+        Self: Sized,
+    {
+        let mut bytes = Vec::with_capacity(self.wire_len());
+        bytes
+            .write_packet_with_checksum(self)
+            .expect("the packet can't be written");
+        bytes
     }
 
     /// Returns a checksum.
@@ -144,6 +921,83 @@ pub trait Packet: AsRef<[u8]> + AsMut<[u8]> {
     }
 }
 
+/// Returns the length of the frame according to the SIZE byte, which may exceed the buffer.
+fn frame_len<P: Packet + ?Sized>(packet: &P) -> usize {
+    P::SIZE_INDEX + packet.size() as usize + 1
+}
+
+/// Returns the length of the frame, checking that the SUM byte follows the header and fits into the buffer.
+fn checked_frame_len<P: Packet + ?Sized>(packet: &P) -> Result<usize, PacketError> {
+    let len = frame_len(packet);
+    if len <= P::DATA_BEGIN_INDEX {
+        return Err(PacketError::FrameTooShort {
+            len,
+            min: P::DATA_BEGIN_INDEX + 1,
+        });
+    }
+    if len > packet.as_ref().len() {
+        return Err(PacketError::BufferTooSmall {
+            len,
+            capacity: packet.as_ref().len(),
+        });
+    }
+
+    Ok(len)
+}
+
+/// Returns the maximum number of DATA bytes, limited by both the buffer and the SIZE byte.
+fn max_data_len<P: Packet + ?Sized>(packet: &P) -> usize {
+    let max_by_size = u8::MAX as usize + P::SIZE_INDEX - P::DATA_BEGIN_INDEX;
+    let max_by_capacity = packet
+        .as_ref()
+        .len()
+        .saturating_sub(P::DATA_BEGIN_INDEX + 1);
+    max_by_size.min(max_by_capacity)
+}
+
+/// Returns `M` DATA bytes starting at `offset`.
+fn data_bytes<P: Packet + ?Sized, const M: usize>(packet: &P, offset: usize) -> [u8; M] {
+    packet.data()[offset..offset + M].try_into().unwrap()
+}
+
+/// Writes `bytes` to the DATA at `offset`, extending the SIZE byte if the bytes end past the current data.
+fn set_data_bytes<'a, P: Packet + ?Sized>(
+    packet: &'a mut P,
+    offset: usize,
+    bytes: &[u8],
+) -> &'a mut P {
+    let begin = P::DATA_BEGIN_INDEX + offset;
+    let data_len = packet.data_len();
+    packet.as_mut()[begin..begin + bytes.len()].copy_from_slice(bytes);
+    if offset + bytes.len() > data_len {
+        let size = P::DATA_BEGIN_INDEX + offset + bytes.len() - P::SIZE_INDEX;
+        packet.set_size(u8::try_from(size).expect("the data doesn't fit into the SIZE byte"));
+    }
+    packet
+}
+
+/// Returns `true` if the SUM byte is located after the header and fits into the buffer.
+fn has_checksum_place<P: Packet + ?Sized>(packet: &P) -> bool {
+    let len = frame_len(packet);
+    len > P::DATA_BEGIN_INDEX && len <= packet.as_ref().len()
+}
+
+/// Formats bytes as a list of hex numbers, e.g. `[e0, ff, 03]`.
+pub(crate) struct HexBytes<'a>(pub(crate) &'a [u8]);
+
+impl std::fmt::Debug for HexBytes<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[")?;
+        for (i, b) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{:02x}", b)?;
+        }
+        f.write_str("]")
+    }
+}
+
 /// A trait that add's additional setters for Response Packets.
 ///
 /// All responses from jvs has report code that will indicate whether the request was processed successfully or not.
@@ -156,16 +1010,93 @@ pub trait ReportField: Packet {
     }
 
     fn report_raw(&self) -> u8 {
-        self.as_ref()[Self::REPORT_INDEX].into()
+        self.as_ref()[Self::REPORT_INDEX]
     }
 
     /// Sets a report code.
+    ///
+    /// The REPORT byte is located before [`Packet::DATA_BEGIN_INDEX`] and [`Packet::set_data`] always counts it in the SIZE byte,
+    /// so this method and [`Packet::set_data`] can be called in any order.
     fn set_report(&mut self, report: impl Into<u8>) -> &mut Self {
         self.as_mut()[Self::REPORT_INDEX] = report.into();
         self
     }
+
+    /// Sets a report code and the packet data at once.
+    ///
+    /// This method will also set the size byte. See [`Packet::set_data`].
+    fn set_report_and_data(&mut self, report: impl Into<u8>, data: &[u8]) -> &mut Self {
+        self.set_report(report).set_data(data)
+    }
+
+    /// Returns `true` if the report code is [`Report::Normal`], i.e. the request was processed successfully.
+    fn is_ok(&self) -> bool {
+        matches!(self.report(), Report::Normal)
+    }
+
+    /// Returns `true` if the report code is anything other than [`Report::Normal`].
+    fn is_error(&self) -> bool {
+        !self.is_ok()
+    }
 }
 
+/// Escapes `src` and appends the result to `dst`.
+///
+/// Every [`SYNC_BYTE`] and [`MARK_BYTE`] is written as [`MARK_BYTE`] followed by the byte's value minus one,
+/// the same way as [`WriteByteExt::write_u8_escaped`] does.
+/// Note that the leading [`SYNC_BYTE`] of a frame shouldn't be passed here, since it would be escaped too.
+pub fn escape_into(src: &[u8], dst: &mut Vec<u8>) {
+    dst.reserve(src.len());
+    for &b in src {
+        if b == SYNC_BYTE || b == MARK_BYTE {
+            dst.extend_from_slice(&[MARK_BYTE, b.wrapping_sub(1)]);
+        } else {
+            dst.push(b);
+        }
+    }
+}
+
+/// Unescapes `src` and appends the result to `dst`.
+///
+/// Every [`MARK_BYTE`] is dropped and the next byte is incremented by one, the same way as [`ReadByteExt::read_u8_escaped`] does.
+/// A trailing [`MARK_BYTE`] without the following byte is ignored.
+pub fn unescape_into(src: &[u8], dst: &mut Vec<u8>) {
+    dst.reserve(src.len());
+    dst.extend(src.iter().copied().unescaped());
+}
+
+/// An iterator adapter that unescapes bytes of the underlying iterator.
+///
+/// Created by [`UnescapeExt::unescaped`].
+#[derive(Debug, Clone)]
+pub struct Unescape<I> {
+    iter: I,
+}
+
+impl<I: Iterator<Item = u8>> Iterator for Unescape<I> {
+    type Item = u8;
+
+    /// Returns the next byte. If it's a [`MARK_BYTE`], the byte after it is returned incremented by one.
+    ///
+    /// A trailing [`MARK_BYTE`] without the following byte yields [`None`].
+    fn next(&mut self) -> Option<u8> {
+        match self.iter.next()? {
+            MARK_BYTE => self.iter.next().map(|b| b.wrapping_add(1)),
+            b => Some(b),
+        }
+    }
+}
+
+/// Additional methods for byte iterators to unescape them lazily.
+pub trait UnescapeExt: Iterator<Item = u8> + Sized {
+    /// Wraps the iterator into [`Unescape`] adapter.
+    fn unescaped(self) -> Unescape<Self> {
+        Unescape { iter: self }
+    }
+}
+
+impl<I: Iterator<Item = u8>> UnescapeExt for I {}
+
 /// Additional methods for [`std::io::Read`] trait to read a single (escaped) byte.
 pub trait ReadByteExt: Read {
     /// Reads a single byte.
@@ -183,6 +1114,34 @@ pub trait ReadByteExt: Read {
         }
         Ok(b)
     }
+
+    /// Same as [`ReadByteExt::read_u8_escaped`], but checks that [`MARK_BYTE`] is followed
+    /// only by an escaped [`SYNC_BYTE`] or [`MARK_BYTE`] (`0xDF` or `0xCF`).
+    ///
+    /// Useful for checking conformance of devices.
+    ///
+    /// # Errors
+    /// Will return [`Err`] with [`PacketError::InvalidEscape`] if any other byte follows [`MARK_BYTE`].
+    fn read_u8_escaped_strict(&mut self) -> io::Result<u8> {
+        let b = self.read_u8()?;
+        if b != MARK_BYTE {
+            return Ok(b);
+        }
+
+        match self.read_u8()? {
+            b @ (0xDF | 0xCF) => Ok(b + 1),
+            b => Err(PacketError::InvalidEscape(b).into()),
+        }
+    }
+
+    /// Same as [`ReadByteExt::read_u8_escaped`], but uses [`Packet::MARK`] of the packet type `P`.
+    fn read_u8_escaped_for<P: Packet>(&mut self) -> io::Result<u8> {
+        let mut b = self.read_u8()?;
+        if b == P::MARK {
+            b = self.read_u8()?.wrapping_add(1);
+        }
+        Ok(b)
+    }
 }
 
 impl<R: Read + ?Sized> ReadByteExt for R {}
@@ -204,6 +1163,17 @@ pub trait WriteByteExt: Write {
             Ok(1)
         }
     }
+
+    /// Same as [`WriteByteExt::write_u8_escaped`], but uses [`Packet::MARK`] and [`Packet::is_escaped_byte`] of the packet type `P`.
+    fn write_u8_escaped_for<P: Packet>(&mut self, b: u8) -> io::Result<usize> {
+        if P::is_escaped_byte(b) {
+            self.write_all(&[P::MARK, b.wrapping_sub(1)])?;
+            Ok(2)
+        } else {
+            self.write_all(&[b])?;
+            Ok(1)
+        }
+    }
 }
 
 impl<W: Write + ?Sized> WriteByteExt for W {}
@@ -212,34 +1182,311 @@ impl<W: Write + ?Sized> WriteByteExt for W {}
 ///
 /// It is better to use [`std::io::BufReader`] to avoid unnecessary syscalls, since we have to read one byte at a time to check for escaped by [`MARK_BYTE`] bytes.
 pub trait ReadPacket: Read {
-    fn read_packet<P: Packet>(&mut self, packet: &mut P) -> io::Result<u8> {
-        let sync = self.read_u8()?;
+    /// Reads a packet from the Reader and returns the length of the frame.
+    ///
+    /// # Errors
+    /// If the Reader reaches EOF after the SYNC byte, but before the whole frame was read,
+    /// the returned error has [`io::ErrorKind::UnexpectedEof`] kind and wraps [`PacketError::TruncatedFrame`].
+    ///
+    /// If the SIZE byte doesn't fit the frame into the packet, the returned error wraps [`PacketError::BufferTooSmall`].
+    /// If the SIZE byte is too small for a minimal frame, the returned error wraps [`PacketError::FrameTooShort`].
+    fn read_packet<P: Packet>(&mut self, packet: &mut P) -> io::Result<usize> {
+        read_frame(self, packet, |r| r.read_u8_escaped_for::<P>())
+    }
+
+    /// Reads a packet from the Reader, consuming at most `max_bytes` wire bytes, including the SYNC byte and escapes.
+    ///
+    /// Bounds the work per call, e.g. in a real-time loop, that must not be stalled by a pathologically escaped frame.
+    /// The Reader is never read past the budget.
+    ///
+    /// # Errors
+    /// Same as [`ReadPacket::read_packet`]. If the frame isn't complete after `max_bytes` wire bytes,
+    /// the returned error wraps [`PacketError::FrameTooLong`].
+    fn read_packet_bounded<P: Packet>(
+        &mut self,
+        packet: &mut P,
+        max_bytes: usize,
+    ) -> io::Result<usize> {
+        let mut consumed = 1;
+        let mut read_u8 = |r: &mut Self| {
+            if consumed >= max_bytes {
+                return Err(PacketError::FrameTooLong { max: max_bytes }.into());
+            }
+            consumed += 1;
+            r.read_u8()
+        };
 
-        if sync != SYNC_BYTE {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!("Expected SYNC byte (0xE0), found: {:#04x}", sync),
-            ));
+        read_frame(self, packet, |r| {
+            let b = read_u8(r)?;
+            if b == P::MARK {
+                return Ok(read_u8(r)?.wrapping_add(1));
+            }
+            Ok(b)
+        })
+    }
+
+    /// Reads packets from the Reader until one satisfies `pred`, e.g. a response from a particular address.
+    ///
+    /// At most `max_frames` frames are read. On success `packet` holds the matching frame and the length of it is returned.
+    ///
+    /// # Errors
+    /// Errors of [`ReadPacket::read_packet`] are returned immediately.
+    /// If none of `max_frames` frames matched, the returned error wraps [`PacketError::NoMatchingFrame`].
+    fn read_packet_until<P: Packet>(
+        &mut self,
+        packet: &mut P,
+        pred: impl Fn(&P) -> bool,
+        max_frames: usize,
+    ) -> io::Result<usize> {
+        for _ in 0..max_frames {
+            let len = self.read_packet(packet)?;
+            if pred(packet) {
+                return Ok(len);
+            }
         }
+
+        Err(PacketError::NoMatchingFrame { frames: max_frames }.into())
+    }
+
+    /// Reads a [`jvs::RequestPacket`](crate::jvs::RequestPacket) backed by a `[u8; N]` array and returns it.
+    ///
+    /// # Errors
+    /// Same as [`ReadPacket::read_packet`].
+    #[cfg(feature = "jvs")]
+    fn read_request<const N: usize>(&mut self) -> io::Result<crate::jvs::RequestPacket<[u8; N]>> {
+        let mut packet = crate::jvs::RequestPacket::new();
+        self.read_packet(&mut packet)?;
+        Ok(packet)
+    }
+
+    /// Reads a [`jvs::ResponsePacket`](crate::jvs::ResponsePacket) backed by a `[u8; N]` array and returns it.
+    ///
+    /// # Errors
+    /// Same as [`ReadPacket::read_packet`].
+    #[cfg(feature = "jvs")]
+    fn read_response<const N: usize>(&mut self) -> io::Result<crate::jvs::ResponsePacket<[u8; N]>> {
+        let mut packet = crate::jvs::ResponsePacket::new();
+        self.read_packet(&mut packet)?;
+        Ok(packet)
+    }
+
+    /// Reads a [`jvs_modified::RequestPacket`](crate::jvs_modified::RequestPacket) backed by a `[u8; N]` array and returns it.
+    ///
+    /// # Errors
+    /// Same as [`ReadPacket::read_packet`].
+    #[cfg(feature = "jvs_modified")]
+    fn read_modified_request<const N: usize>(
+        &mut self,
+    ) -> io::Result<crate::jvs_modified::RequestPacket<[u8; N]>> {
+        let mut packet = crate::jvs_modified::RequestPacket::new();
+        self.read_packet(&mut packet)?;
+        Ok(packet)
+    }
+
+    /// Reads a [`jvs_modified::ResponsePacket`](crate::jvs_modified::ResponsePacket) backed by a `[u8; N]` array and returns it.
+    ///
+    /// # Errors
+    /// Same as [`ReadPacket::read_packet`].
+    #[cfg(feature = "jvs_modified")]
+    fn read_modified_response<const N: usize>(
+        &mut self,
+    ) -> io::Result<crate::jvs_modified::ResponsePacket<[u8; N]>> {
+        let mut packet = crate::jvs_modified::ResponsePacket::new();
+        self.read_packet(&mut packet)?;
+        Ok(packet)
+    }
+
+    /// Reads a packet from the Reader without unescaping.
+    ///
+    /// **This bypasses escaping**: bytes after the SYNC byte are transferred verbatim, so a [`MARK_BYTE`] is read as data.
+    /// Useful when the transport already handles byte stuffing or for feeding pre-unescaped test vectors.
+    /// The frame is still delimited by the SYNC and SIZE bytes.
+    ///
+    /// # Errors
+    /// Same as [`ReadPacket::read_packet`].
+    fn read_packet_raw<P: Packet>(&mut self, packet: &mut P) -> io::Result<usize> {
+        read_frame(self, packet, |r| r.read_u8())
+    }
+
+    /// Reads a packet without blocking on a partially received frame.
+    ///
+    /// Reads bytes until the Reader has nothing more to give ([`io::ErrorKind::WouldBlock`], [`io::ErrorKind::TimedOut`] or EOF)
+    /// and returns `Ok(None)` if the frame isn't complete yet. The progress is kept in `state` and in the packet itself,
+    /// so the next call with the same `packet` and `state` continues where the previous one stopped.
+    /// Returns the length of the packet once the whole frame was read.
+    ///
+    /// Bytes before the [`SYNC_BYTE`] are skipped, and an unescaped [`SYNC_BYTE`] in the middle of a frame starts a new one.
+    ///
+    /// # Errors
+    /// Will return [`Err`] if the frame doesn't fit into the packet or its SIZE byte is too small for a minimal frame.
+    /// The `state` is reset in that case.
+    fn try_read_packet<P: Packet>(
+        &mut self,
+        packet: &mut P,
+        state: &mut ReadState,
+    ) -> io::Result<Option<usize>> {
+        loop {
+            let b = match self.read_u8() {
+                Ok(b) => b,
+                Err(e)
+                    if matches!(
+                        e.kind(),
+                        io::ErrorKind::WouldBlock
+                            | io::ErrorKind::TimedOut
+                            | io::ErrorKind::UnexpectedEof
+                    ) =>
+                {
+                    return Ok(None)
+                }
+                Err(e) => return Err(e),
+            };
+
+            if state.push(packet, b)? {
+                return Ok(Some(packet.len_of_packet()));
+            }
+        }
+    }
+}
+
+impl<R: Read + ?Sized> ReadPacket for R {}
+
+/// Progress of a partially read frame.
+///
+/// Used by [`ReadPacket::try_read_packet`] to continue reading a frame across calls.
+#[derive(Debug, Clone, Default)]
+pub struct ReadState {
+    pos: usize,
+    escaped: bool,
+}
+
+impl ReadState {
+    pub const fn new() -> Self {
+        Self {
+            pos: 0,
+            escaped: false,
+        }
+    }
+
+    /// Returns the number of (unescaped) bytes of the current frame read so far.
+    ///
+    /// `0` means that the [`SYNC_BYTE`] is not found yet.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Discards the partially read frame.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Pushes a single wire byte into the packet.
+    ///
+    /// Returns `true` if the frame is complete. The state is reset afterwards, so the next byte starts a new frame.
+    /// Returns [`PacketError::FrameTooShort`] as soon as the SIZE byte leaves no place for the header and the SUM byte.
+    pub(crate) fn push<P: Packet>(&mut self, packet: &mut P, b: u8) -> Result<bool, PacketError> {
+        if b == P::SYNC {
+            packet.as_mut()[0] = P::SYNC;
+            self.pos = 1;
+            self.escaped = false;
+            return Ok(false);
+        }
+        if self.pos == 0 {
+            return Ok(false);
+        }
+
+        let b = if self.escaped {
+            self.escaped = false;
+            b.wrapping_add(1)
+        } else if b == P::MARK {
+            self.escaped = true;
+            return Ok(false);
+        } else {
+            b
+        };
+
         let buf = packet.as_mut();
-        buf[0] = sync;
+        if self.pos >= buf.len() {
+            let capacity = buf.len();
+            self.reset();
+            return Err(PacketError::BufferTooSmall {
+                len: capacity + 1,
+                capacity,
+            });
+        }
+        buf[self.pos] = b;
+        self.pos += 1;
 
-        // Read to the SIZE byte first
-        for b in &mut buf[1..=P::SIZE_INDEX] {
-            *b = self.read_u8_escaped()?;
+        if self.pos == P::SIZE_INDEX + 1 {
+            if let Err(e @ PacketError::FrameTooShort { .. }) = checked_frame_len(packet) {
+                self.reset();
+                return Err(e);
+            }
+        } else if self.pos > P::SIZE_INDEX && self.pos == frame_len(packet) {
+            self.reset();
+            return Ok(true);
         }
 
-        let len = buf[P::SIZE_INDEX] as usize + P::SIZE_INDEX;
+        Ok(false)
+    }
+}
+
+/// Reads a frame delimited by the SYNC and SIZE bytes, reading every byte after the SYNC byte with `read_byte`.
+fn read_frame<R: Read + ?Sized, P: Packet>(
+    reader: &mut R,
+    packet: &mut P,
+    mut read_byte: impl FnMut(&mut R) -> io::Result<u8>,
+) -> io::Result<usize> {
+    let sync = reader.read_u8()?;
+
+    if sync != P::SYNC {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "Expected SYNC byte ({:#04x}), found: {:#04x}",
+                P::SYNC,
+                sync
+            ),
+        ));
+    }
+    let buf = packet.as_mut();
+    buf[0] = sync;
 
-        for b in &mut buf[P::SIZE_INDEX + 1..=len] {
-            *b = self.read_u8_escaped()?;
+    // Read to the SIZE byte first
+    for (i, b) in buf[1..=P::SIZE_INDEX].iter_mut().enumerate() {
+        *b = read_byte(reader).map_err(|e| truncated(e, P::SIZE_INDEX + 1, i + 1))?;
+    }
+
+    let len = buf[P::SIZE_INDEX] as usize + P::SIZE_INDEX;
+    if len < P::DATA_BEGIN_INDEX {
+        return Err(PacketError::FrameTooShort {
+            len: len + 1,
+            min: P::DATA_BEGIN_INDEX + 1,
+        }
+        .into());
+    }
+    if len >= buf.len() {
+        return Err(PacketError::BufferTooSmall {
+            len: len + 1,
+            capacity: buf.len(),
         }
+        .into());
+    }
 
-        Ok(packet.len_of_packet() as u8)
+    for (i, b) in buf[P::SIZE_INDEX + 1..=len].iter_mut().enumerate() {
+        *b = read_byte(reader).map_err(|e| truncated(e, len + 1, P::SIZE_INDEX + 1 + i))?;
     }
+
+    Ok(packet.len_of_packet())
 }
 
-impl<R: Read + ?Sized> ReadPacket for R {}
+/// Converts an [`io::ErrorKind::UnexpectedEof`] that occurred in the middle of a frame into [`PacketError::TruncatedFrame`].
+fn truncated(err: io::Error, expected: usize, got: usize) -> io::Error {
+    if err.kind() == io::ErrorKind::UnexpectedEof {
+        PacketError::TruncatedFrame { expected, got }.into()
+    } else {
+        err
+    }
+}
 
 /// A helper trait which implemented for [`std::io::Write`]. Contains methods for writing [`Packet`]s to the Writer.
 ///
@@ -251,56 +1498,289 @@ pub trait WritePacket: Write {
     /// Use [`Self::write_packet_with_checksum`] to calculate checksum while writing bytes.
     ///
     /// # Errors
-    /// Will return [`Err`] if [`Packet::len_of_packet`] less than [`Packet::DATA_BEGIN_INDEX`] + 1 which is nonsense.
+    /// Will return [`Err`] if [`Packet::len_of_packet`] less than [`Packet::DATA_BEGIN_INDEX`] + 1 which is nonsense,
+    /// or if the SIZE byte points past the end of the buffer.
     fn write_packet<P: Packet>(&mut self, packet: &P) -> io::Result<usize> {
-        if packet.len_of_packet() < P::DATA_BEGIN_INDEX + 1 {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidInput,
-                format!(
-                    "The size of packet is can't be less than {}",
-                    P::DATA_BEGIN_INDEX + 1
-                ),
-            ));
-        }
+        check_writable(packet)?;
         let mut bytes_written = 1;
 
-        self.write_u8(SYNC_BYTE)?;
+        self.write_u8(P::SYNC)?;
 
         for &b in &packet.as_slice()[1..] {
-            bytes_written += self.write_u8_escaped(b)?;
+            bytes_written += self.write_u8_escaped_for::<P>(b)?;
         }
 
         Ok(bytes_written)
     }
 
+    /// Similar to [`WritePacket::write_packet`], but checks the packet with [`Packet::validate`] before writing anything.
+    ///
+    /// Catches packets, that were modified without calling [`Packet::calculate_checksum`] afterwards.
+    ///
+    /// # Errors
+    /// Will return [`Err`] with [`io::ErrorKind::InvalidInput`] kind, that wraps the [`PacketError`] returned by [`Packet::validate`].
+    fn write_packet_checked<P: Packet>(&mut self, packet: &P) -> io::Result<usize> {
+        packet
+            .validate()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        self.write_packet(packet)
+    }
+
     /// Similar to [`WritePacket::write_packet`], but it will calculate checksum while writing bytes to the writer.
     ///
+    /// The SUM byte stored in the packet is ignored, the freshly calculated checksum is written instead.
+    ///
     /// # Errors
-    /// Will return [`Err`] if [`Packet::len_of_packet`] less than [`Packet::DATA_BEGIN_INDEX`] + 1 which is nonsense.
+    /// Will return [`Err`] if [`Packet::len_of_packet`] less than [`Packet::DATA_BEGIN_INDEX`] + 1 which is nonsense,
+    /// or if the SIZE byte points past the end of the buffer.
     fn write_packet_with_checksum<P: Packet>(&mut self, packet: &P) -> io::Result<usize> {
-        if packet.len_of_packet() < P::DATA_BEGIN_INDEX + 1 {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidInput,
-                format!(
-                    "The size of packet is can't be less than {}",
-                    P::DATA_BEGIN_INDEX + 1
-                ),
-            ));
-        }
-
-        self.write_u8(SYNC_BYTE)?;
-        let mut bytes_written: usize = 2;
+        check_writable(packet)?;
+
+        self.write_u8(P::SYNC)?;
+        let mut bytes_written: usize = 1;
         let mut checksum: u8 = 0;
-        dbg!(&packet.as_slice()[1..packet.len_of_packet()]);
         for &b in &packet.as_slice()[1..packet.len_of_packet() - 1] {
-            bytes_written += self.write_u8_escaped(b)?;
+            bytes_written += self.write_u8_escaped_for::<P>(b)?;
             checksum = checksum.wrapping_add(b);
         }
 
-        self.write_u8_escaped(checksum)?;
+        bytes_written += self.write_u8_escaped_for::<P>(checksum)?;
 
         Ok(bytes_written)
     }
+
+    /// Writes a packet to the Writer without escaping.
+    ///
+    /// **This bypasses escaping**: the frame, including the stored SUM byte, is written verbatim,
+    /// so the output is not valid on a JVS bus if it contains [`SYNC_BYTE`] or [`MARK_BYTE`] after the SYNC byte.
+    /// Useful when the transport already handles byte stuffing or for producing canonical test vectors.
+    ///
+    /// # Errors
+    /// Same as [`WritePacket::write_packet`].
+    fn write_packet_raw<P: Packet>(&mut self, packet: &P) -> io::Result<usize> {
+        check_writable(packet)?;
+        self.write_all(packet.as_slice())?;
+
+        Ok(packet.len_of_packet())
+    }
+
+    /// Writes packets in order with [`WritePacket::write_packet_with_checksum`] and returns the total number of written bytes.
+    ///
+    /// # Errors
+    /// Stops at the first error, so the packets before the failed one are already written.
+    fn write_packets<P: Packet>(&mut self, packets: &[P]) -> io::Result<usize> {
+        packets.iter().try_fold(0, |written, packet| {
+            Ok(written + self.write_packet_with_checksum(packet)?)
+        })
+    }
 }
 
 impl<W: Write + ?Sized> WritePacket for W {}
+
+/// Checks that the SIZE byte of the packet describes a frame, that can be written.
+pub(crate) fn check_writable<P: Packet>(packet: &P) -> io::Result<()> {
+    let len = frame_len(packet);
+    if len < P::DATA_BEGIN_INDEX + 1 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "The size of packet is can't be less than {}",
+                P::DATA_BEGIN_INDEX + 1
+            ),
+        ));
+    }
+    if len > packet.as_ref().len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "The size of packet ({len}) exceeds the buffer ({})",
+                packet.as_ref().len()
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Default)]
+    struct CustomPacket([u8; 32]);
+
+    impl AsRef<[u8]> for CustomPacket {
+        fn as_ref(&self) -> &[u8] {
+            &self.0
+        }
+    }
+
+    impl AsMut<[u8]> for CustomPacket {
+        fn as_mut(&mut self) -> &mut [u8] {
+            &mut self.0
+        }
+    }
+
+    impl Packet for CustomPacket {
+        const SIZE_INDEX: usize = 1;
+        const DATA_BEGIN_INDEX: usize = 3;
+        const DESTINATION_INDEX: usize = 2;
+        const KIND: FrameKind = FrameKind::Request;
+        const SYNC: u8 = 0xAA;
+
+        fn is_escaped_byte(b: u8) -> bool {
+            b == Self::SYNC || b == Self::MARK || b == 0xE1
+        }
+    }
+
+    #[test]
+    fn test_custom_sync() {
+        let mut packet = CustomPacket::default();
+        packet
+            .set_sync()
+            .set_dest(0x01)
+            .set_data(&[SYNC_BYTE, 0x02])
+            .calculate_checksum();
+        assert_eq!(packet.sync(), 0xAA);
+
+        let mut writer = io::Cursor::new(vec![]);
+        writer.write_packet(&packet).unwrap();
+        let wire = writer.into_inner();
+        assert_eq!(wire[0], 0xAA);
+
+        let mut read = CustomPacket::default();
+        io::Cursor::new(&wire).read_packet(&mut read).unwrap();
+        assert_eq!(read.as_slice(), packet.as_slice());
+
+        let err = io::Cursor::new(&[SYNC_BYTE, 0x01])
+            .read_packet(&mut read)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_custom_escaping() {
+        let mut packet = CustomPacket::default();
+        packet
+            .set_sync()
+            .set_dest(0x01)
+            .set_data(&[0xE1, 0xAA, SYNC_BYTE])
+            .calculate_checksum();
+
+        let mut writer = io::Cursor::new(vec![]);
+        writer.write_packet_with_checksum(&packet).unwrap();
+        let wire = writer.into_inner();
+        assert_eq!(
+            wire,
+            [0xAA, 0x05, 0x01, MARK_BYTE, 0xE0, MARK_BYTE, 0xA9, SYNC_BYTE, 0x71]
+        );
+
+        let mut read = CustomPacket::default();
+        io::Cursor::new(&wire).read_packet(&mut read).unwrap();
+        assert_eq!(read.as_slice(), packet.as_slice());
+
+        let mut read = CustomPacket::default();
+        let mut state = ReadState::new();
+        let len = io::Cursor::new(&wire)
+            .try_read_packet(&mut read, &mut state)
+            .unwrap();
+        assert_eq!(len, Some(packet.len_of_packet()));
+        assert_eq!(read.as_slice(), packet.as_slice());
+    }
+
+    #[test]
+    fn test_escape_round_trip() {
+        let data = [0x01, SYNC_BYTE, 0x02, MARK_BYTE, MARK_BYTE, 0xFF];
+        let mut escaped = vec![];
+        escape_into(&data, &mut escaped);
+        assert_eq!(escaped, [0x01, 0xD0, 0xDF, 0x02, 0xD0, 0xCF, 0xD0, 0xCF, 0xFF]);
+
+        let mut unescaped = vec![];
+        unescape_into(&escaped, &mut unescaped);
+        assert_eq!(unescaped, data);
+    }
+
+    #[test]
+    fn test_escape_plain_data() {
+        let data = [0x00, 0x01, 0xCF, 0xDF, 0xE1, 0xFF];
+        let mut escaped = vec![];
+        escape_into(&data, &mut escaped);
+        assert_eq!(escaped, data);
+
+        let mut unescaped = vec![];
+        unescape_into(&escaped, &mut unescaped);
+        assert_eq!(unescaped, data);
+    }
+
+    #[test]
+    fn test_unescape_iterator() {
+        let unescaped: Vec<u8> = [0xE0, 0xFF, 0xD0, 0xDF, 0xD0, 0xCF, 0x01]
+            .into_iter()
+            .unescaped()
+            .collect();
+        assert_eq!(unescaped, [0xE0, 0xFF, SYNC_BYTE, MARK_BYTE, 0x01]);
+
+        let mut iter = [0x01, MARK_BYTE].into_iter().unescaped();
+        assert_eq!(iter.next(), Some(0x01));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_read_u8_escaped_strict() {
+        let mut reader = io::Cursor::new([0x01, 0xD0, 0xDF, 0xD0, 0xCF, 0xD0, 0x00]);
+        assert_eq!(reader.read_u8_escaped_strict().unwrap(), 0x01);
+        assert_eq!(reader.read_u8_escaped_strict().unwrap(), SYNC_BYTE);
+        assert_eq!(reader.read_u8_escaped_strict().unwrap(), MARK_BYTE);
+
+        let err = reader.read_u8_escaped_strict().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            err.to_string(),
+            PacketError::InvalidEscape(0x00).to_string()
+        );
+    }
+
+    #[test]
+    fn test_report_display() {
+        assert_eq!(Report::Normal.to_string(), "normal");
+        assert_eq!(
+            Report::IncorrectDataSize.to_string(),
+            "incorrect number of parameters"
+        );
+        assert_eq!(Report::InvalidData.to_string(), "invalid data");
+        assert_eq!(Report::Busy.to_string(), "device I/O busy");
+        assert_eq!(Report::from(0x10).to_string(), "unknown report code");
+    }
+
+    #[test]
+    fn test_unescape_trailing_mark() {
+        let mut unescaped = vec![];
+        unescape_into(&[0x01, MARK_BYTE], &mut unescaped);
+        assert_eq!(unescaped, [0x01]);
+    }
+}
+
+/// Helpers for property-based tests of the packet types.
+#[cfg(test)]
+pub(crate) mod strategy {
+    use proptest::{collection::vec, num::u8::ANY, strategy::Strategy};
+
+    use super::Packet;
+
+    /// Generates well-formed packets with random header and data bytes, a valid SIZE byte and a matching checksum.
+    ///
+    /// The amount of data is limited by both the buffer of the packet and the SIZE byte.
+    pub(crate) fn valid_packet<P: Packet + Default + std::fmt::Debug>() -> impl Strategy<Value = P>
+    {
+        let capacity = P::default().as_ref().len();
+        let max_by_size = u8::MAX as usize + P::SIZE_INDEX - P::DATA_BEGIN_INDEX;
+        let max_data = max_by_size.min(capacity - P::DATA_BEGIN_INDEX - 1);
+
+        (vec(ANY, P::DATA_BEGIN_INDEX - 1), vec(ANY, 0..=max_data)).prop_map(|(header, data)| {
+            let mut packet = P::default();
+            packet.as_mut()[1..P::DATA_BEGIN_INDEX].copy_from_slice(&header);
+            packet.set_sync().finalize(&data);
+            packet
+        })
+    }
+}
@@ -0,0 +1,362 @@
+use crate::{Packet, PacketError, ReadState};
+
+/// The part of a frame that [`PacketParser`] is currently waiting for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParserState {
+    /// Skipping bytes until the [`SYNC_BYTE`](crate::SYNC_BYTE) is found.
+    Sync,
+    /// Reading bytes up to the SIZE byte.
+    Header,
+    /// Reading the rest of the frame, which length is known from the SIZE byte.
+    Body,
+}
+
+/// An incremental parser, that accepts bytes in chunks of any size and emits complete packets.
+///
+/// The parser doesn't depend on any IO model, so it can be used with async runtimes,
+/// DMA buffers or anything else that provides received bytes in chunks.
+///
+/// # Example
+/// ```
+/// use jvs_packets::{jvs::RequestPacket, Packet, PacketParser};
+///
+/// let mut parser = PacketParser::<RequestPacket>::new();
+/// assert!(parser.feed(&[0xE0, 0xFF, 0x03]).is_none());
+///
+/// let packet = parser.feed(&[0x01, 0x02, 0x05]).unwrap();
+/// assert_eq!(packet.data(), &[0x01, 0x02]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PacketParser<P> {
+    packet: P,
+    state: ReadState,
+    pending: Vec<u8>,
+}
+
+impl<P: Packet + Default> PacketParser<P> {
+    pub fn new() -> Self {
+        Self::with_packet(P::default())
+    }
+}
+
+impl<P: Packet + Default> Default for PacketParser<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: Packet> PacketParser<P> {
+    /// Creates a parser that assembles frames in the given packet.
+    ///
+    /// Useful for packets without [`Default`] implementation, e.g. the ones backed by a `Vec<u8>`.
+    pub fn with_packet(packet: P) -> Self {
+        Self {
+            packet,
+            state: ReadState::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Returns the part of a frame that the parser is currently waiting for.
+    pub fn state(&self) -> ParserState {
+        match self.state.pos() {
+            0 => ParserState::Sync,
+            pos if pos <= P::SIZE_INDEX => ParserState::Header,
+            _ => ParserState::Body,
+        }
+    }
+
+    /// Feeds a chunk of (escaped) bytes to the parser.
+    ///
+    /// Returns a packet once a whole frame has been received.
+    /// If the chunk contains bytes after the end of the frame, they are kept and processed by the next call,
+    /// so call `feed(&[])` to get the rest of the frames.
+    ///
+    /// A chunk may end anywhere, even right after a [`MARK_BYTE`](crate::MARK_BYTE),
+    /// in which case the escaped byte is taken from the beginning of the next chunk.
+    ///
+    /// Frames that don't fit into the packet or have a SIZE byte too small for a minimal frame are silently dropped.
+    pub fn feed(&mut self, bytes: &[u8]) -> Option<P>
+    where
+        P: Clone,
+    {
+        let mut pending = std::mem::take(&mut self.pending);
+        pending.extend_from_slice(bytes);
+
+        for (i, &b) in pending.iter().enumerate() {
+            if let Ok(true) = self.state.push(&mut self.packet, b) {
+                pending.drain(..=i);
+                self.pending = pending;
+                return Some(self.packet.clone());
+            }
+        }
+
+        pending.clear();
+        self.pending = pending;
+        None
+    }
+}
+
+/// Finds (escaped) frames of packet type `P` in a captured blob and returns their `start..end` wire byte ranges.
+///
+/// Bytes between frames are skipped. Escaping is honored, so an escaped SYNC byte doesn't start a new frame.
+/// A frame interrupted by a SYNC byte or by the end of the blob yields [`PacketError::TruncatedFrame`]
+/// with the unescaped lengths, and scanning continues from the interrupting SYNC byte.
+/// A frame with a SIZE byte too small for a minimal frame yields [`PacketError::FrameTooShort`],
+/// and scanning continues after the SIZE byte.
+///
+/// # Example
+/// ```
+/// use jvs_packets::{jvs::RequestPacket, scan_frames};
+///
+/// let blob = [0xE0, 0xFF, 0x03, 0xD0, 0xDF, 0x01, 0xE3, 0x00, 0xE0, 0x01, 0x02, 0x10, 0x13];
+/// let frames: Vec<_> = scan_frames::<RequestPacket>(&blob).collect();
+/// assert_eq!(frames, [Ok((0, 7)), Ok((8, 13))]);
+/// ```
+pub fn scan_frames<P: Packet>(
+    blob: &[u8],
+) -> impl Iterator<Item = Result<(usize, usize), PacketError>> + '_ {
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        let start = pos + blob[pos..].iter().position(|&b| b == P::SYNC)?;
+        let mut got = 1;
+        let mut expected = P::SIZE_INDEX + 1;
+        let mut escaped = false;
+
+        for (i, &b) in blob.iter().enumerate().skip(start + 1) {
+            if b == P::SYNC {
+                pos = i;
+                return Some(Err(PacketError::TruncatedFrame { expected, got }));
+            }
+            let b = if escaped {
+                escaped = false;
+                b.wrapping_add(1)
+            } else if b == P::MARK {
+                escaped = true;
+                continue;
+            } else {
+                b
+            };
+
+            got += 1;
+            if got == P::SIZE_INDEX + 1 {
+                expected = b as usize + P::SIZE_INDEX + 1;
+                if expected <= P::DATA_BEGIN_INDEX {
+                    pos = i + 1;
+                    return Some(Err(PacketError::FrameTooShort {
+                        len: expected,
+                        min: P::DATA_BEGIN_INDEX + 1,
+                    }));
+                }
+            } else if got == expected {
+                pos = i + 1;
+                return Some(Ok((start, pos)));
+            }
+        }
+
+        pos = blob.len();
+        Some(Err(PacketError::TruncatedFrame { expected, got }))
+    })
+}
+
+/// Finds the first whole (escaped) frame of packet type `P` in `buf`.
+///
+/// Returns the number of bytes before the frame, that can be discarded, and the wire length of the frame,
+/// or [`None`] if `buf` doesn't hold a whole frame yet. Escaped bytes are counted twice,
+/// and a SYNC byte in the middle of a frame starts a new one, like [`PacketParser`] does.
+/// A frame with a SIZE byte too small for a minimal frame yields [`PacketError::FrameTooShort`],
+/// and a frame that doesn't fit into `P` yields [`PacketError::BufferTooSmall`].
+///
+/// # Example
+/// ```
+/// use jvs_packets::{frame_length, jvs::RequestPacket};
+///
+/// let wire = [0x00, 0xE0, 0xFF, 0x03, 0xD0, 0xDF, 0x01, 0xE3];
+/// assert_eq!(frame_length::<RequestPacket>(&wire[..7]), Ok(None));
+/// assert_eq!(frame_length::<RequestPacket>(&wire), Ok(Some((1, 7))));
+/// ```
+pub fn frame_length<P: Packet + Default>(
+    buf: &[u8],
+) -> Result<Option<(usize, usize)>, PacketError> {
+    let mut packet = P::default();
+    let mut state = ReadState::new();
+    let mut start = 0;
+    for (i, &b) in buf.iter().enumerate() {
+        if b == P::SYNC {
+            start = i;
+        }
+        if state.push(&mut packet, b)? {
+            return Ok(Some((start, i + 1 - start)));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Decodes the first complete frame in `bytes` into `packet`.
+///
+/// Returns the number of bytes up to the end of the frame, including any garbage before it,
+/// or [`None`] if `bytes` doesn't contain a complete frame. Frames that don't fit into the packet are skipped.
+#[cfg(feature = "bytes")]
+pub(crate) fn decode<P: Packet>(packet: &mut P, bytes: &[u8]) -> Option<usize> {
+    let mut state = ReadState::new();
+    bytes
+        .iter()
+        .position(|&b| matches!(state.push(packet, b), Ok(true)))
+        .map(|i| i + 1)
+}
+
+#[cfg(all(test, feature = "jvs"))]
+mod tests {
+    use super::*;
+    use crate::jvs::RequestPacket;
+
+    const REQUEST_DATA: [u8; 6] = [0xE0, 0xFF, 0x03, 0xD0, 0x01, 0xD3];
+    const REQUEST_WIRE: [u8; 7] = [0xE0, 0xFF, 0x03, 0xD0, 0xCF, 0x01, 0xD3];
+
+    #[test]
+    fn test_parser_split_frame() {
+        let mut parser = PacketParser::<RequestPacket>::new();
+        assert_eq!(parser.state(), ParserState::Sync);
+
+        assert!(parser.feed(&REQUEST_WIRE[..2]).is_none());
+        assert_eq!(parser.state(), ParserState::Header);
+        assert!(parser.feed(&REQUEST_WIRE[2..4]).is_none());
+        assert_eq!(parser.state(), ParserState::Body);
+
+        let packet = parser.feed(&REQUEST_WIRE[4..]).unwrap();
+        assert_eq!(packet.as_slice(), REQUEST_DATA);
+        assert_eq!(parser.state(), ParserState::Sync);
+    }
+
+    #[test]
+    fn test_parser_chunk_ends_on_mark_byte() {
+        let mut parser = PacketParser::<RequestPacket>::new();
+        let wire = [0xE0, 0xFF, 0x03, 0xD0, 0xDF, 0x01, 0xE3];
+
+        assert!(parser.feed(&wire[..4]).is_none());
+        let packet = parser.feed(&wire[4..]).unwrap();
+        assert_eq!(packet.as_slice(), [0xE0, 0xFF, 0x03, 0xE0, 0x01, 0xE3]);
+        assert_eq!(packet.data(), &[0xE0, 0x01]);
+    }
+
+    #[test]
+    fn test_parser_multiple_frames() {
+        let mut parser = PacketParser::<RequestPacket>::new();
+        let mut bytes = vec![0x00, 0x01];
+        bytes.extend_from_slice(&REQUEST_WIRE);
+        bytes.extend_from_slice(&REQUEST_WIRE);
+
+        assert_eq!(parser.feed(&bytes).unwrap().as_slice(), REQUEST_DATA);
+        assert_eq!(parser.feed(&[]).unwrap().as_slice(), REQUEST_DATA);
+        assert!(parser.feed(&[]).is_none());
+    }
+
+    #[test]
+    fn test_frame_length() {
+        for len in 0..REQUEST_WIRE.len() {
+            assert_eq!(
+                frame_length::<RequestPacket>(&REQUEST_WIRE[..len]),
+                Ok(None)
+            );
+        }
+        assert_eq!(
+            frame_length::<RequestPacket>(&REQUEST_WIRE),
+            Ok(Some((0, REQUEST_WIRE.len())))
+        );
+
+        let mut buf = REQUEST_WIRE.to_vec();
+        buf.extend_from_slice(&REQUEST_WIRE[..3]);
+        assert_eq!(
+            frame_length::<RequestPacket>(&buf),
+            Ok(Some((0, REQUEST_WIRE.len())))
+        );
+
+        assert_eq!(frame_length::<RequestPacket>(&REQUEST_WIRE[1..]), Ok(None));
+    }
+
+    #[test]
+    fn test_frame_length_skips_garbage() {
+        let mut buf = vec![0x00, 0x01, 0xD0];
+        buf.extend_from_slice(&REQUEST_WIRE);
+        assert_eq!(
+            frame_length::<RequestPacket>(&buf),
+            Ok(Some((3, REQUEST_WIRE.len())))
+        );
+
+        // A SYNC byte in the middle of a frame starts a new one.
+        let mut buf = REQUEST_WIRE[..4].to_vec();
+        buf.extend_from_slice(&REQUEST_WIRE);
+        assert_eq!(
+            frame_length::<RequestPacket>(&buf),
+            Ok(Some((4, REQUEST_WIRE.len())))
+        );
+    }
+
+    #[test]
+    fn test_frame_length_too_short() {
+        assert_eq!(
+            frame_length::<RequestPacket>(&[0xE0, 0x01, 0x00, 0xE0]),
+            Err(PacketError::FrameTooShort { len: 3, min: 4 })
+        );
+    }
+
+    #[test]
+    fn test_headerless_frames() {
+        use crate::jvs::ResponsePacket;
+        let mut parser = PacketParser::<RequestPacket>::new();
+        assert!(parser.feed(&[0xE0, 0x01, 0x00]).is_none());
+        assert_eq!(parser.state(), ParserState::Sync);
+        assert_eq!(parser.feed(&REQUEST_WIRE).unwrap().as_slice(), REQUEST_DATA);
+
+        // SIZE 1 leaves no place for the REPORT byte of a response.
+        let mut parser = PacketParser::<ResponsePacket>::new();
+        assert!(parser.feed(&[0xE0, 0x00, 0x01, 0x01]).is_none());
+
+        let mut packet = RequestPacket::<[u8; 16]>::new();
+        let err = crate::ReadPacket::read_packet(&mut &[0xE0, 0x01, 0x00, 0x01][..], &mut packet)
+            .unwrap_err();
+        assert_eq!(
+            err.into_inner().unwrap().downcast_ref::<PacketError>(),
+            Some(&PacketError::FrameTooShort { len: 3, min: 4 })
+        );
+
+        let mut blob = vec![0xE0, 0x01, 0x00];
+        blob.extend_from_slice(&REQUEST_WIRE);
+        let frames: Vec<_> = scan_frames::<RequestPacket>(&blob).collect();
+        assert_eq!(
+            frames,
+            [
+                Err(PacketError::FrameTooShort { len: 3, min: 4 }),
+                Ok((3, 10))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_frames() {
+        // Garbage, a frame with an escaped SYNC byte, a frame interrupted by the next one, a frame and a truncated frame.
+        let mut blob = vec![0x00, 0x01];
+        blob.extend_from_slice(&[0xE0, 0xFF, 0x03, 0xD0, 0xDF, 0x01, 0xE3]);
+        blob.extend_from_slice(&[0xE0, 0xFF, 0x05, 0x01]);
+        blob.extend_from_slice(&REQUEST_WIRE);
+        blob.extend_from_slice(&REQUEST_WIRE[..5]);
+
+        let frames: Vec<_> = scan_frames::<RequestPacket>(&blob).collect();
+        assert_eq!(
+            frames,
+            [
+                Ok((2, 9)),
+                Err(PacketError::TruncatedFrame {
+                    expected: 8,
+                    got: 4
+                }),
+                Ok((13, 20)),
+                Err(PacketError::TruncatedFrame {
+                    expected: 6,
+                    got: 4
+                }),
+            ]
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use std::io::{self, Read};
+
+use crate::packet::check_writable;
+use crate::Packet;
+
+/// An adapter, that implements [`std::io::Read`] for a packet. Created by [`Packet::reader`].
+///
+/// Yields the escaped on-wire bytes of the frame, exactly what [`WritePacket::write_packet_with_checksum`](crate::WritePacket::write_packet_with_checksum)
+/// would write: the SYNC byte, the escaped frame and a freshly calculated escaped checksum.
+///
+/// # Example
+/// ```
+/// use jvs_packets::{jvs::RequestPacket, Packet};
+///
+/// let packet = RequestPacket::<[u8; 256]>::from_slice(&[0xE0, 0xFF, 0x03, 0xD0, 0x01, 0xD3]);
+/// let mut wire = vec![];
+/// std::io::copy(&mut packet.reader(), &mut wire).unwrap();
+///
+/// assert_eq!(wire, [0xE0, 0xFF, 0x03, 0xD0, 0xCF, 0x01, 0xD3]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PacketReader<'a, P> {
+    packet: &'a P,
+    pos: usize,
+    checksum: u8,
+    escaped: Option<u8>,
+}
+
+impl<'a, P: Packet> PacketReader<'a, P> {
+    pub(crate) fn new(packet: &'a P) -> Self {
+        Self {
+            packet,
+            pos: 0,
+            checksum: 0,
+            escaped: None,
+        }
+    }
+
+    /// Returns the next wire byte or [`None`] if the whole frame was read.
+    fn next_byte(&mut self) -> Option<u8> {
+        if let Some(b) = self.escaped.take() {
+            return Some(b);
+        }
+
+        let len = self.packet.len_of_packet();
+        let b = match self.pos {
+            0 => {
+                self.pos += 1;
+                return Some(P::SYNC);
+            }
+            pos if pos < len - 1 => {
+                let b = self.packet.as_slice()[pos];
+                self.checksum = self.checksum.wrapping_add(b);
+                b
+            }
+            pos if pos == len - 1 => self.checksum,
+            _ => return None,
+        };
+        self.pos += 1;
+
+        if P::is_escaped_byte(b) {
+            self.escaped = Some(b.wrapping_sub(1));
+            Some(P::MARK)
+        } else {
+            Some(b)
+        }
+    }
+}
+
+impl<P: Packet> Read for PacketReader<'_, P> {
+    /// # Errors
+    /// Same as [`WritePacket::write_packet`](crate::WritePacket::write_packet), reported by the first call.
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pos == 0 {
+            check_writable(self.packet)?;
+        }
+
+        let mut read = 0;
+        for b in buf.iter_mut() {
+            match self.next_byte() {
+                Some(next) => *b = next,
+                None => break,
+            }
+            read += 1;
+        }
+
+        Ok(read)
+    }
+}
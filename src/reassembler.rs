@@ -0,0 +1,108 @@
+use crate::{Packet, PacketError};
+
+/// Collects DATA of fragmented packets back into a contiguous payload.
+///
+/// The counterpart of the `fragment` constructor of packets. Headers are ignored, only DATA bytes are stitched together.
+///
+/// # Example
+/// ```
+/// use jvs_packets::{jvs::RequestPacket, Reassembler};
+///
+/// # fn main() -> Result<(), jvs_packets::PacketError> {
+/// let payload = vec![0x01; 600];
+/// let packets = RequestPacket::<[u8; 256]>::fragment(0x01, &payload);
+///
+/// assert_eq!(Reassembler::reassemble(&packets)?, payload);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Reassembler {
+    data: Vec<u8>,
+    fragments: usize,
+}
+
+impl Reassembler {
+    pub const fn new() -> Self {
+        Self {
+            data: Vec::new(),
+            fragments: 0,
+        }
+    }
+
+    /// Reassembles all `packets` at once.
+    ///
+    /// # Errors
+    /// See [`Reassembler::push`].
+    pub fn reassemble<'a, P: Packet + 'a>(
+        packets: impl IntoIterator<Item = &'a P>,
+    ) -> Result<Vec<u8>, PacketError> {
+        let mut reassembler = Self::new();
+        for packet in packets {
+            reassembler.push(packet)?;
+        }
+        Ok(reassembler.finish())
+    }
+
+    /// Appends DATA of the next fragment.
+    ///
+    /// # Errors
+    /// Will return [`PacketError::FragmentChecksum`] with the index of the fragment if its checksum is invalid.
+    /// The fragment is not appended in that case.
+    pub fn push<P: Packet>(&mut self, packet: &P) -> Result<&mut Self, PacketError> {
+        if !packet.verify_checksum() {
+            return Err(PacketError::FragmentChecksum {
+                index: self.fragments,
+            });
+        }
+
+        self.data.extend_from_slice(packet.data());
+        self.fragments += 1;
+        Ok(self)
+    }
+
+    /// Returns the number of fragments pushed so far.
+    pub fn fragments(&self) -> usize {
+        self.fragments
+    }
+
+    /// Returns the reassembled payload.
+    pub fn finish(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+#[cfg(all(test, feature = "jvs"))]
+mod tests {
+    use super::*;
+    use crate::jvs::RequestPacket;
+
+    #[test]
+    fn test_reassemble() {
+        let payload: Vec<u8> = (0..600).map(|i| i as u8).collect();
+        let packets = RequestPacket::<[u8; 256]>::fragment(0x01, &payload);
+
+        assert_eq!(packets.len(), 3);
+        assert_eq!(Reassembler::reassemble(&packets).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_reassemble_invalid_checksum() {
+        let payload: Vec<u8> = (0..600).map(|i| i as u8).collect();
+        let mut packets = RequestPacket::<[u8; 256]>::fragment(0x01, &payload);
+        let checksum = packets[1].checksum();
+        packets[1].set_checksum(checksum.wrapping_add(1));
+
+        let mut reassembler = Reassembler::new();
+        reassembler.push(&packets[0]).unwrap();
+        assert_eq!(
+            reassembler.push(&packets[1]).unwrap_err(),
+            PacketError::FragmentChecksum { index: 1 }
+        );
+        assert_eq!(reassembler.fragments(), 1);
+        assert_eq!(
+            Reassembler::reassemble(&packets).unwrap_err(),
+            PacketError::FragmentChecksum { index: 1 }
+        );
+    }
+}